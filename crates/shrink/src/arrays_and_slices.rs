@@ -1,5 +1,7 @@
-use crate::Classify;
-use std::collections::{hash_map::DefaultHasher, BTreeSet};
+use crate::class_key::ListLen;
+use crate::{Cardinality, ClassKey, ClassSpace, Classify, ClassifyContext, Granularity};
+use crate::{StableHash, StableHasher};
+use std::collections::{BTreeMap, BTreeSet};
 use std::hash::{Hash, Hasher};
 
 impl<T: Classify, const N: usize> Classify for [T; N]
@@ -11,6 +13,18 @@ where
     fn classify(&self) -> Self::Output {
         self.as_slice().classify()
     }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        self.as_slice().classify_with(ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        self.as_slice().class_key()
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        self.as_slice().class_key_with(ctx)
+    }
 }
 
 impl<T: Classify> Classify for [T]
@@ -20,26 +34,71 @@ where
     type Output = u64;
 
     fn classify(&self) -> Self::Output {
-        let mut hasher = DefaultHasher::new();
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let mut hasher = StableHasher::new();
 
-        // Hash the length of the slice, classifying all
+        // Hash the length bucket of the slice, by default classifying all
         // items with two or more elements as the same
-        match self.len() {
-            0 => 0.hash(&mut hasher),
-            1 => 1.hash(&mut hasher),
-            _ => 2.hash(&mut hasher),
-        }
+        (ctx.len_bucket(self.len()) as i32).stable_hash(&mut hasher);
 
-        // Get a unique, sorted list of classifications
-        let unique_classifications = self.iter().map(Classify::classify).collect::<BTreeSet<_>>();
+        match ctx.granularity {
+            Granularity::Coarse => {
+                // Get a unique, sorted list of classifications
+                let unique_classifications = self
+                    .iter()
+                    .map(|item| item.classify_with(ctx))
+                    .collect::<BTreeSet<_>>();
 
-        // Then hash those
-        for classification in unique_classifications {
-            classification.hash(&mut hasher);
+                // Then hash those
+                for classification in unique_classifications {
+                    classification.stable_hash(&mut hasher);
+                }
+            }
+            Granularity::Fine => {
+                // Along with the length bucket of how many items have each
+                for (classification, count) in count_classes(self, |item| item.classify_with(ctx)) {
+                    classification.stable_hash(&mut hasher);
+                    (ctx.len_bucket(count) as i32).stable_hash(&mut hasher);
+                }
+            }
         }
 
         hasher.finish()
     }
+
+    fn class_key(&self) -> ClassKey {
+        self.class_key_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        let items = match ctx.granularity {
+            Granularity::Coarse => self
+                .iter()
+                .map(|item| item.class_key_with(ctx))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            Granularity::Fine => count_classes(self, |item| item.class_key_with(ctx))
+                .into_iter()
+                .map(|(key, count)| {
+                    let count = match ctx.len_range(count) {
+                        ListLen::Exact(n) => format!("x{n}"),
+                        ListLen::AtLeast(n) => format!("x{n}+"),
+                        ListLen::Range(min, end) => format!("x{min}..{end}"),
+                    };
+                    ClassKey::Tuple(vec![key, ClassKey::Label(count)])
+                })
+                .collect(),
+        };
+
+        ClassKey::List {
+            len: ctx.len_range(self.len()),
+            items,
+        }
+    }
 }
 
 impl<T: Classify> Classify for Vec<T>
@@ -51,11 +110,71 @@ where
     fn classify(&self) -> Self::Output {
         self.as_slice().classify()
     }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        self.as_slice().classify_with(ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        self.as_slice().class_key()
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        self.as_slice().class_key_with(ctx)
+    }
+}
+
+/// The number of items with each classification, sorted by classification
+fn count_classes<T, C: Ord>(items: &[T], classify: impl Fn(&T) -> C) -> BTreeMap<C, usize> {
+    let mut counts = BTreeMap::new();
+    for item in items {
+        *counts.entry(classify(item)).or_insert(0) += 1;
+    }
+    counts
+}
+
+impl<T: Classify, const N: usize> ClassSpace for [T; N]
+where
+    T::Output: Hash + Eq + Ord,
+{
+    fn cardinality() -> Cardinality {
+        Cardinality::Unbounded
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        std::iter::empty()
+    }
+}
+
+impl<T: Classify> ClassSpace for [T]
+where
+    T::Output: Hash + Eq + Ord,
+{
+    fn cardinality() -> Cardinality {
+        Cardinality::Unbounded
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        std::iter::empty()
+    }
+}
+
+impl<T: Classify> ClassSpace for Vec<T>
+where
+    T::Output: Hash + Eq + Ord,
+{
+    fn cardinality() -> Cardinality {
+        Cardinality::Unbounded
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        std::iter::empty()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Classify;
+    use crate::{Classify, ClassifyContext, Granularity};
 
     #[test]
     fn test_array_classification_length() {
@@ -98,6 +217,51 @@ mod tests {
         assert_ne!(arr1.classify(), arr2.classify());
     }
 
+    #[test]
+    fn test_len_buckets() {
+        let ctx = ClassifyContext {
+            len_buckets: vec![0, 1, 10].into(),
+            ..ClassifyContext::DEFAULT
+        };
+
+        let short = [1u8; 2];
+        let long = [1u8; 12];
+
+        assert_eq!(short.classify(), long.classify());
+        assert_ne!(short.classify_with(&ctx), long.classify_with(&ctx));
+        assert_eq!(
+            short.class_key_with(&ctx).to_string(),
+            "[Positive; len=1..10]"
+        );
+        assert_eq!(
+            ClassifyContext::DEFAULT.len_buckets,
+            [0, 1, 2].as_slice(),
+            "the default buckets must keep the default keys stable"
+        );
+        assert_eq!(
+            short.classify_with(&ClassifyContext::DEFAULT),
+            short.classify()
+        );
+    }
+
+    #[test]
+    fn test_fine_granularity() {
+        let ctx = ClassifyContext {
+            granularity: Granularity::Fine,
+            ..ClassifyContext::DEFAULT
+        };
+
+        let one_zero = [0u8, 1, 2];
+        let two_zeros = [0u8, 0, 1];
+
+        assert_eq!(one_zero.classify(), two_zeros.classify());
+        assert_ne!(one_zero.classify_with(&ctx), two_zeros.classify_with(&ctx));
+        assert_eq!(
+            two_zeros.class_key_with(&ctx).to_string(),
+            "[(Positive, x1), (Zero, x2+); len>=2]"
+        );
+    }
+
     #[test]
     fn test_mixed_classification() {
         let arr1 = [1u8, 2, 3];
@@ -1,9 +1,29 @@
-use crate::Classify;
+use crate::{Cardinality, ClassKey, ClassSpace, Classify};
 
 impl Classify for bool {
     type Output = bool;
 
+    const MAX_CLASSES: Option<u128> = Some(2);
+
     fn classify(&self) -> Self::Output {
         *self
     }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(self)
+    }
+}
+
+impl ClassSpace for bool {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(2)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        [false, true].into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
 }
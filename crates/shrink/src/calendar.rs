@@ -0,0 +1,376 @@
+use crate::{Cardinality, CivilDate, ClassKey, ClassSpace, Classify};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Offset, TimeDelta, TimeZone};
+
+/// Classifies a date by its place in the calendar, in place of the coarse
+/// classes of the date itself.
+///
+/// Month ends, leap days, year boundaries, weekends, and for `DateTime`,
+/// time zone offsets and daylight saving transitions each get their own
+/// classes, so a representative of each is kept.
+///
+/// ```
+/// # #[cfg(feature = "chrono")] {
+/// use chrono::NaiveDate;
+/// use shrink::{Calendar, CalendarDay, CalendarWeek, Classify};
+///
+/// let leap_day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+/// let class = Calendar(leap_day).classify();
+///
+/// assert_eq!(class.day, CalendarDay::LeapDay);
+/// assert_eq!(class.week, CalendarWeek::Weekday);
+/// # }
+/// ```
+///
+/// Derived fields can be classified this way with `#[classify(calendar)]`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Calendar<T>(pub T);
+
+/// The classification of a [`Calendar`] date
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarClassification {
+    pub day: CalendarDay,
+    pub week: CalendarWeek,
+    pub zone: CalendarZone,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CalendarDay {
+    /// Jan 1st
+    FirstOfYear,
+    /// Dec 31st
+    LastOfYear,
+    /// Feb 29th
+    LeapDay,
+    /// The first day of any other month
+    FirstOfMonth,
+    /// The last day of any other month
+    LastOfMonth,
+    /// Any other day
+    Other,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CalendarWeek {
+    /// Monday to Friday
+    Weekday,
+    /// Saturday or Sunday
+    Weekend,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CalendarZone {
+    /// A date without a time zone
+    Naive,
+    /// A time with no offset from UTC
+    Utc,
+    /// A time with an offset from UTC
+    Offset,
+    /// A time within an hour of a change in offset, such as the start or
+    /// end of daylight saving time
+    DstTransition,
+}
+
+/// Dates that can be classified with [`Calendar`]
+pub trait CalendarClassify {
+    /// The zone classes dates of this type can have
+    const ZONES: &'static [CalendarZone];
+
+    fn classify_calendar(&self) -> CalendarClassification;
+}
+
+const DAYS: [CalendarDay; 6] = [
+    CalendarDay::FirstOfYear,
+    CalendarDay::LastOfYear,
+    CalendarDay::LeapDay,
+    CalendarDay::FirstOfMonth,
+    CalendarDay::LastOfMonth,
+    CalendarDay::Other,
+];
+
+const WEEKS: [CalendarWeek; 2] = [CalendarWeek::Weekday, CalendarWeek::Weekend];
+
+impl CalendarClassification {
+    /// The day and week classes of `date`, in the given zone class
+    pub fn of(date: CivilDate, zone: CalendarZone) -> Self {
+        let day = match (date.month, date.day) {
+            (1, 1) => CalendarDay::FirstOfYear,
+            (12, 31) => CalendarDay::LastOfYear,
+            (2, 29) => CalendarDay::LeapDay,
+            (_, 1) => CalendarDay::FirstOfMonth,
+            (_, day) if day == date.days_in_month() => CalendarDay::LastOfMonth,
+            _ => CalendarDay::Other,
+        };
+        let week = if date.is_weekend() {
+            CalendarWeek::Weekend
+        } else {
+            CalendarWeek::Weekday
+        };
+
+        Self { day, week, zone }
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::Struct(vec![
+            ("day".to_string(), ClassKey::debug(&self.day)),
+            ("week".to_string(), ClassKey::debug(&self.week)),
+            ("zone".to_string(), ClassKey::debug(&self.zone)),
+        ])
+    }
+}
+
+impl<T: CalendarClassify> Classify for Calendar<T> {
+    type Output = CalendarClassification;
+
+    const MAX_CLASSES: Option<u128> = Some((DAYS.len() * WEEKS.len() * T::ZONES.len()) as u128);
+
+    fn classify(&self) -> Self::Output {
+        self.0.classify_calendar()
+    }
+
+    fn class_key(&self) -> ClassKey {
+        self.classify().class_key()
+    }
+}
+
+impl<T: CalendarClassify> ClassSpace for Calendar<T> {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite((DAYS.len() * WEEKS.len() * T::ZONES.len()) as u128)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        DAYS.into_iter().flat_map(|day| {
+            WEEKS.into_iter().flat_map(move |week| {
+                T::ZONES
+                    .iter()
+                    .map(move |&zone| CalendarClassification { day, week, zone })
+            })
+        })
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, class.class_key()))
+    }
+}
+
+impl<T: CalendarClassify + ?Sized> CalendarClassify for &T {
+    const ZONES: &'static [CalendarZone] = T::ZONES;
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        (**self).classify_calendar()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CalendarClassify for NaiveDate {
+    const ZONES: &'static [CalendarZone] = &[CalendarZone::Naive];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        CalendarClassification::of(CivilDate::from(*self), CalendarZone::Naive)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CalendarClassify for NaiveDateTime {
+    const ZONES: &'static [CalendarZone] = &[CalendarZone::Naive];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        self.date().classify_calendar()
+    }
+}
+
+/// Every zone class, since the offsets a time zone uses, and whether it
+/// ever changes them, are only known at runtime
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> CalendarClassify for DateTime<Tz> {
+    const ZONES: &'static [CalendarZone] = &[
+        CalendarZone::Utc,
+        CalendarZone::Offset,
+        CalendarZone::DstTransition,
+    ];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        let offset = self.offset().fix();
+        let utc = self.naive_utc();
+        let offset_at = |utc: Option<NaiveDateTime>| {
+            utc.map(|utc| self.timezone().offset_from_utc_datetime(&utc).fix())
+        };
+
+        let hour = TimeDelta::hours(1);
+        let changes = [
+            offset_at(utc.checked_sub_signed(hour)),
+            offset_at(utc.checked_add_signed(hour)),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|other| other != offset);
+
+        let zone = if changes {
+            CalendarZone::DstTransition
+        } else if offset.local_minus_utc() == 0 {
+            CalendarZone::Utc
+        } else {
+            CalendarZone::Offset
+        };
+
+        CalendarClassification::of(CivilDate::from(self.date_naive()), zone)
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, LocalResult, NaiveTime, Utc};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_calendar_days() {
+        let day = |year, month, day| Calendar(date(year, month, day)).classify().day;
+
+        assert_eq!(day(2024, 1, 1), CalendarDay::FirstOfYear);
+        assert_eq!(day(2023, 12, 31), CalendarDay::LastOfYear);
+        assert_eq!(day(2024, 2, 29), CalendarDay::LeapDay);
+        assert_eq!(day(2023, 2, 28), CalendarDay::LastOfMonth);
+        assert_eq!(day(2024, 2, 28), CalendarDay::Other);
+        assert_eq!(day(2024, 4, 30), CalendarDay::LastOfMonth);
+        assert_eq!(day(2024, 5, 1), CalendarDay::FirstOfMonth);
+        assert_eq!(day(2024, 5, 15), CalendarDay::Other);
+    }
+
+    #[test]
+    fn test_calendar_weeks() {
+        let week = |year, month, day| Calendar(date(year, month, day)).classify().week;
+
+        assert_eq!(week(2024, 5, 3), CalendarWeek::Weekday);
+        assert_eq!(week(2024, 5, 4), CalendarWeek::Weekend);
+        assert_eq!(week(2024, 5, 5), CalendarWeek::Weekend);
+        assert_eq!(week(2024, 5, 6), CalendarWeek::Weekday);
+    }
+
+    #[test]
+    fn test_calendar_zones() {
+        let naive = date(2024, 5, 6).and_time(NaiveTime::MIN);
+        assert_eq!(Calendar(naive).classify().zone, CalendarZone::Naive);
+
+        let utc = Utc.from_utc_datetime(&naive);
+        assert_eq!(Calendar(utc).classify().zone, CalendarZone::Utc);
+
+        let offset = FixedOffset::east_opt(3600)
+            .unwrap()
+            .from_utc_datetime(&naive);
+        assert_eq!(Calendar(offset).classify().zone, CalendarZone::Offset);
+
+        // The local date decides the day
+        let may_day = date(2024, 5, 1).and_time(NaiveTime::MIN);
+        let offset = FixedOffset::west_opt(3600)
+            .unwrap()
+            .from_utc_datetime(&may_day);
+        assert_eq!(Calendar(offset).classify().day, CalendarDay::LastOfMonth);
+    }
+
+    /// UTC+1 in winter and UTC+2 in summer, changing at 01:00 UTC on the
+    /// last Sundays of March and October of 2024
+    #[derive(Debug, Copy, Clone)]
+    struct Cet;
+
+    impl Cet {
+        fn offset_at(utc: &NaiveDateTime) -> FixedOffset {
+            let start = date(2024, 3, 31).and_hms_opt(1, 0, 0).unwrap();
+            let end = date(2024, 10, 27).and_hms_opt(1, 0, 0).unwrap();
+            let hours = if (start..end).contains(utc) { 2 } else { 1 };
+            FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+    }
+
+    impl TimeZone for Cet {
+        type Offset = FixedOffset;
+
+        fn from_offset(_offset: &FixedOffset) -> Self {
+            Cet
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        /// The offsets, summer time first, that map `local` back to itself
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let offsets: Vec<FixedOffset> = [2, 1]
+                .into_iter()
+                .map(|hours| FixedOffset::east_opt(hours * 3600).unwrap())
+                .filter(|&offset| Self::offset_at(&(*local - offset)) == offset)
+                .collect();
+
+            match offsets[..] {
+                [] => LocalResult::None,
+                [offset] => LocalResult::Single(offset),
+                [earliest, latest] => LocalResult::Ambiguous(earliest, latest),
+                _ => unreachable!(),
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            Self::offset_at(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            Self::offset_at(utc)
+        }
+    }
+
+    #[test]
+    fn test_calendar_dst_transitions() {
+        let zone = |month, day, hour, minute| {
+            let utc = date(2024, month, day).and_hms_opt(hour, minute, 0).unwrap();
+            Calendar(Cet.from_utc_datetime(&utc)).classify().zone
+        };
+
+        assert_eq!(zone(3, 31, 0, 0), CalendarZone::DstTransition);
+        assert_eq!(zone(3, 31, 1, 30), CalendarZone::DstTransition);
+        assert_eq!(zone(3, 31, 2, 1), CalendarZone::Offset);
+        assert_eq!(zone(3, 30, 23, 59), CalendarZone::Offset);
+        assert_eq!(zone(10, 27, 0, 30), CalendarZone::DstTransition);
+        assert_eq!(zone(7, 1, 12, 0), CalendarZone::Offset);
+
+        // Local times skipped in spring and repeated in autumn
+        let local = |month, day| date(2024, month, day).and_hms_opt(2, 30, 0).unwrap();
+        assert!(matches!(
+            Cet.from_local_datetime(&local(3, 31)),
+            LocalResult::None
+        ));
+        assert!(matches!(
+            Cet.from_local_datetime(&local(10, 27)),
+            LocalResult::Ambiguous(_, _)
+        ));
+        assert!(matches!(
+            Cet.from_local_datetime(&local(7, 1)),
+            LocalResult::Single(_)
+        ));
+    }
+
+    #[test]
+    fn test_calendar_space() {
+        assert_eq!(Calendar::<NaiveDate>::MAX_CLASSES, Some(12));
+        assert_eq!(Calendar::<DateTime<Utc>>::MAX_CLASSES, Some(36));
+        assert_eq!(Calendar::<DateTime<FixedOffset>>::MAX_CLASSES, Some(36));
+        assert_eq!(
+            Calendar::<DateTime<Cet>>::cardinality(),
+            Cardinality::Finite(36)
+        );
+
+        let keyed: Vec<_> = Calendar::<NaiveDate>::keyed_classes().collect();
+        assert_eq!(keyed.len(), 12);
+        assert_eq!(
+            keyed[0].1.to_string(),
+            "{day: FirstOfYear, week: Weekday, zone: Naive}"
+        );
+
+        let leap_day = Calendar(date(2024, 2, 29));
+        assert!(keyed
+            .iter()
+            .any(|(class, key)| *class == leap_day.classify() && *key == leap_day.class_key()));
+    }
+}
@@ -0,0 +1,427 @@
+use crate::ContextError;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar date, independent of any date library
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CivilDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// The dates that are plausible, rather than probably an error.
+///
+/// Dates from `start` up to, but not including, `end` are plausible. By
+/// default that is from Jan 1st, 1970 up to 2050. Either bound can be
+/// relative to today, as told by the [`Clock`] of a
+/// [`ClassifyContext`](crate::ClassifyContext).
+///
+/// Ranges parse from `start..end`, where each bound is a year such as
+/// `1900`, a date such as `1900-06-30`, or `now`, optionally moved by a
+/// number of years, such as `now-100y` or `now+50y`.
+///
+/// ```
+/// use shrink::DateRange;
+///
+/// let historical: DateRange = "1900..2100".parse().unwrap();
+/// let recent: DateRange = "now-10y..now+1y".parse().unwrap();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    pub start: DateBound,
+    pub end: DateBound,
+}
+
+/// A bound of a [`DateRange`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DateBound {
+    Date(CivilDate),
+    /// The date this many years from today, negative for the past
+    YearsFromToday(i32),
+}
+
+/// Tells [`DateRange`]s relative to today what day it is
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Clock {
+    /// Today's date in UTC, by the system clock
+    #[default]
+    System,
+    /// Always the same date, for reproducible classifications
+    Fixed(CivilDate),
+}
+
+impl CivilDate {
+    pub const fn new(year: i32, month: u32, day: u32) -> Self {
+        Self { year, month, day }
+    }
+
+    /// The date `days` days after Jan 1st, 1970. Dates past the range of
+    /// an `i32` year saturate to the first or last day of that range.
+    pub fn from_unix_days(days: i64) -> Self {
+        // From Howard Hinnant's `civil_from_days`
+        let days = days.saturating_add(719_468);
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        match i32::try_from(year) {
+            Ok(year) => Self::new(year, month as u32, day as u32),
+            Err(_) if year < 0 => Self::new(i32::MIN, 1, 1),
+            Err(_) => Self::new(i32::MAX, 12, 31),
+        }
+    }
+
+    /// The number of days from Jan 1st, 1970 to this date, negative before it
+    pub fn unix_days(&self) -> i64 {
+        // From Howard Hinnant's `days_from_civil`
+        let (month, day) = (i64::from(self.month), i64::from(self.day));
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub fn is_leap_year(&self) -> bool {
+        self.year % 4 == 0 && (self.year % 100 != 0 || self.year % 400 == 0)
+    }
+
+    /// The number of days in this date's month
+    pub fn days_in_month(&self) -> u32 {
+        match self.month {
+            2 if self.is_leap_year() => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Whether this date is a Saturday or a Sunday
+    pub fn is_weekend(&self) -> bool {
+        // Jan 1st, 1970 was a Thursday, day 3 counting from Monday
+        (self.unix_days() + 3).rem_euclid(7) >= 5
+    }
+
+    /// The same day of the year, `years` years later
+    pub fn add_years(&self, years: i32) -> Self {
+        Self::new(self.year.saturating_add(years), self.month, self.day)
+    }
+}
+
+impl DateRange {
+    /// Jan 1st, 1970 up to 2050
+    pub const DEFAULT: Self = Self::years(1970, 2050);
+
+    /// From the start of the year `start` up to the start of the year `end`
+    pub const fn years(start: i32, end: i32) -> Self {
+        Self {
+            start: DateBound::Date(CivilDate::new(start, 1, 1)),
+            end: DateBound::Date(CivilDate::new(end, 1, 1)),
+        }
+    }
+
+    /// From `years_before` years before today up to `years_after` years
+    /// after today
+    pub const fn relative(years_before: i32, years_after: i32) -> Self {
+        Self {
+            start: DateBound::YearsFromToday(-years_before),
+            end: DateBound::YearsFromToday(years_after),
+        }
+    }
+
+    /// Whether `date` is in the range, asking `clock` for today's date if
+    /// a bound is relative to it
+    pub fn contains(&self, date: CivilDate, clock: &Clock) -> bool {
+        self.start.resolve(clock) <= date && date < self.end.resolve(clock)
+    }
+}
+
+impl Default for DateRange {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl DateBound {
+    /// The date of this bound, asking `clock` for today's date if needed
+    pub fn resolve(&self, clock: &Clock) -> CivilDate {
+        match self {
+            DateBound::Date(date) => *date,
+            DateBound::YearsFromToday(years) => clock.today().add_years(*years),
+        }
+    }
+}
+
+impl Clock {
+    pub fn today(&self) -> CivilDate {
+        match self {
+            Clock::System => CivilDate::from(SystemTime::now()),
+            Clock::Fixed(date) => *date,
+        }
+    }
+}
+
+/// The UTC date of a system time
+impl From<SystemTime> for CivilDate {
+    fn from(time: SystemTime) -> Self {
+        let days = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => (since.as_secs() / 86_400) as i64,
+            Err(err) => {
+                let before = err.duration();
+                let secs = before.as_secs() + u64::from(before.subsec_nanos() > 0);
+                -(secs.div_ceil(86_400) as i64)
+            }
+        };
+        CivilDate::from_unix_days(days)
+    }
+}
+
+impl fmt::Display for CivilDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+impl fmt::Display for DateBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateBound::Date(date) => write!(f, "{date}"),
+            DateBound::YearsFromToday(0) => f.write_str("now"),
+            DateBound::YearsFromToday(years) => write!(f, "now{years:+}y"),
+        }
+    }
+}
+
+/// Parses a `YYYY-MM-DD` date
+impl FromStr for CivilDate {
+    type Err = ContextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ContextError::new(format!("invalid date `{s}`, expected YYYY-MM-DD"));
+
+        // The year may be negative, so split the month and day off the end
+        let mut parts = s.rsplitn(3, '-');
+        let (Some(day), Some(month), Some(year)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let date = Self::new(
+            year.parse().map_err(|_| invalid())?,
+            month.parse().map_err(|_| invalid())?,
+            day.parse().map_err(|_| invalid())?,
+        );
+        if !(1..=12).contains(&date.month) || !(1..=date.days_in_month()).contains(&date.day) {
+            return Err(invalid());
+        }
+        Ok(date)
+    }
+}
+
+/// Parses `start..end`
+impl FromStr for DateRange {
+    type Err = ContextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((start, end)) = s.split_once("..") else {
+            return Err(ContextError::new(format!(
+                "invalid date range `{s}`, expected `start..end`"
+            )));
+        };
+
+        let range = Self {
+            start: start.trim().parse()?,
+            end: end.trim().parse()?,
+        };
+
+        // Bounds of different kinds are only comparable once today is known
+        let empty = match (range.start, range.end) {
+            (DateBound::Date(start), DateBound::Date(end)) => start >= end,
+            (DateBound::YearsFromToday(start), DateBound::YearsFromToday(end)) => start >= end,
+            _ => false,
+        };
+        if empty {
+            return Err(ContextError::new(format!(
+                "empty date range `{s}`, the start must be before the end"
+            )));
+        }
+        Ok(range)
+    }
+}
+
+/// Parses a year, a `YYYY-MM-DD` date, or `now` with an optional number
+/// of years, such as `now-10y`
+impl FromStr for DateBound {
+    type Err = ContextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            ContextError::new(format!(
+                "invalid date bound `{s}`, expected a year, YYYY-MM-DD or now+Ny"
+            ))
+        };
+
+        if let Some(offset) = s.strip_prefix("now") {
+            if offset.is_empty() {
+                return Ok(DateBound::YearsFromToday(0));
+            }
+            let years = offset.strip_suffix('y').ok_or_else(invalid)?;
+            if !years.starts_with(['+', '-']) {
+                return Err(invalid());
+            }
+            return Ok(DateBound::YearsFromToday(
+                years.parse().map_err(|_| invalid())?,
+            ));
+        }
+
+        match s.parse::<i32>() {
+            Ok(year) => Ok(DateBound::Date(CivilDate::new(year, 1, 1))),
+            Err(_) => Ok(DateBound::Date(s.parse().map_err(|_| invalid())?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_civil_date() {
+        assert_eq!("1970-01-01".parse(), Ok(CivilDate::new(1970, 1, 1)));
+        assert_eq!("-44-03-15".parse(), Ok(CivilDate::new(-44, 3, 15)));
+        assert!("1970-13-01".parse::<CivilDate>().is_err());
+        assert!("2023-02-29".parse::<CivilDate>().is_err());
+        assert!("2023-04-31".parse::<CivilDate>().is_err());
+        assert_eq!("2024-02-29".parse(), Ok(CivilDate::new(2024, 2, 29)));
+        assert!("1970".parse::<CivilDate>().is_err());
+        assert_eq!(CivilDate::new(1970, 1, 1).to_string(), "1970-01-01");
+    }
+
+    #[test]
+    fn test_unix_days() {
+        for (days, date) in [
+            (0, CivilDate::new(1970, 1, 1)),
+            (-1, CivilDate::new(1969, 12, 31)),
+            (59, CivilDate::new(1970, 3, 1)),
+            (11_016, CivilDate::new(2000, 2, 29)),
+            (-719_468, CivilDate::new(0, 3, 1)),
+        ] {
+            assert_eq!(CivilDate::from_unix_days(days), date);
+            assert_eq!(date.unix_days(), days);
+        }
+
+        for days in -1_000_000..1_000_000 {
+            assert_eq!(CivilDate::from_unix_days(days).unix_days(), days);
+        }
+
+        // Years past `i32` saturate instead of wrapping
+        assert_eq!(
+            CivilDate::from_unix_days(i64::MAX),
+            CivilDate::new(i32::MAX, 12, 31)
+        );
+        assert_eq!(
+            CivilDate::from_unix_days(i64::MIN),
+            CivilDate::new(i32::MIN, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_calendar_helpers() {
+        assert!(CivilDate::new(2000, 1, 1).is_leap_year());
+        assert!(CivilDate::new(2024, 1, 1).is_leap_year());
+        assert!(!CivilDate::new(1900, 1, 1).is_leap_year());
+        assert!(!CivilDate::new(2023, 1, 1).is_leap_year());
+
+        assert_eq!(CivilDate::new(2024, 2, 1).days_in_month(), 29);
+        assert_eq!(CivilDate::new(2023, 2, 1).days_in_month(), 28);
+        assert_eq!(CivilDate::new(2023, 4, 1).days_in_month(), 30);
+        assert_eq!(CivilDate::new(2023, 12, 1).days_in_month(), 31);
+
+        // Saturday, Sunday and Monday
+        assert!(CivilDate::new(2024, 5, 4).is_weekend());
+        assert!(CivilDate::new(2024, 5, 5).is_weekend());
+        assert!(!CivilDate::new(2024, 5, 6).is_weekend());
+        assert!(!CivilDate::new(1970, 1, 1).is_weekend());
+        assert!(CivilDate::new(1969, 12, 28).is_weekend());
+    }
+
+    #[test]
+    fn test_parse_date_range() {
+        assert_eq!("1900..2100".parse(), Ok(DateRange::years(1900, 2100)));
+        assert_eq!(
+            "now-100y..now+50y".parse(),
+            Ok(DateRange::relative(100, 50))
+        );
+        assert_eq!(
+            "1950-06-30..now".parse(),
+            Ok(DateRange {
+                start: DateBound::Date(CivilDate::new(1950, 6, 30)),
+                end: DateBound::YearsFromToday(0),
+            })
+        );
+
+        for range in [
+            "1970-01-01..2050-01-01",
+            "now-100y..now+50y",
+            "2000-01-01..now",
+        ] {
+            assert_eq!(range.parse::<DateRange>().unwrap().to_string(), range);
+        }
+
+        assert!("1900".parse::<DateRange>().is_err());
+        assert!("1900..later".parse::<DateRange>().is_err());
+        assert!("now5y..2100".parse::<DateRange>().is_err());
+        assert!("now+5..2100".parse::<DateRange>().is_err());
+        assert!("2023-02-30..2100".parse::<DateRange>().is_err());
+
+        // Empty and inverted ranges
+        assert!("2000..2000".parse::<DateRange>().is_err());
+        assert!("2100..1900".parse::<DateRange>().is_err());
+        assert!("2000-06-30..2000-06-01".parse::<DateRange>().is_err());
+        assert!("now..now".parse::<DateRange>().is_err());
+        assert!("now+5y..now-5y".parse::<DateRange>().is_err());
+    }
+
+    #[test]
+    fn test_date_range_contains() {
+        let clock = Clock::Fixed(CivilDate::new(2024, 5, 1));
+
+        let range = DateRange::DEFAULT;
+        assert!(range.contains(CivilDate::new(1970, 1, 1), &clock));
+        assert!(range.contains(CivilDate::new(2049, 12, 31), &clock));
+        assert!(!range.contains(CivilDate::new(1969, 12, 31), &clock));
+        assert!(!range.contains(CivilDate::new(2050, 1, 1), &clock));
+
+        let range = DateRange::relative(10, 1);
+        assert!(range.contains(CivilDate::new(2014, 5, 1), &clock));
+        assert!(!range.contains(CivilDate::new(2014, 4, 30), &clock));
+        assert!(range.contains(CivilDate::new(2025, 4, 30), &clock));
+        assert!(!range.contains(CivilDate::new(2025, 5, 1), &clock));
+    }
+
+    #[test]
+    fn test_system_clock() {
+        assert!(Clock::System.today() > CivilDate::new(2024, 1, 1));
+    }
+}
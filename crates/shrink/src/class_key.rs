@@ -0,0 +1,174 @@
+use std::fmt;
+
+/// A structured, human readable classification.
+///
+/// Where [`Classify::classify`](crate::Classify::classify) collapses
+/// composite values into an opaque `u64`,
+/// [`Classify::class_key`](crate::Classify::class_key) keeps the shape of
+/// the value, so it is possible to see *why* two items land in different
+/// classes. Two values have equal class keys exactly when they have equal
+/// classifications.
+///
+/// ```text
+/// {first: Zero, second: NonEmpty, third: Some(B)}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClassKey {
+    /// The classification of a single value, such as `Zero` or `NonEmpty`
+    Label(String),
+    /// A classification that can't be described, shown as its stable hash
+    Opaque(u64),
+    /// Ordered, unnamed parts, as in tuples and tuple structs
+    Tuple(Vec<ClassKey>),
+    /// Named fields, as in structs
+    Struct(Vec<(String, ClassKey)>),
+    /// An enum variant and its fields
+    Variant(String, Box<ClassKey>),
+    /// The unique classifications of a slice's items
+    List { len: ListLen, items: Vec<ClassKey> },
+}
+
+/// The length of a list, as seen by its classification
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ListLen {
+    Exact(usize),
+    AtLeast(usize),
+    /// From the first length up to, but not including, the second
+    Range(usize, usize),
+}
+
+impl ClassKey {
+    /// Labels a classification with its `Debug` representation
+    pub fn debug<T: fmt::Debug>(classification: &T) -> Self {
+        ClassKey::Label(format!("{classification:?}"))
+    }
+
+    /// A key for a variant without any fields
+    pub fn unit_variant(name: impl Into<String>) -> Self {
+        ClassKey::Variant(name.into(), Box::new(ClassKey::Tuple(Vec::new())))
+    }
+}
+
+impl fmt::Display for ClassKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClassKey::Label(label) => f.write_str(label),
+            ClassKey::Opaque(hash) => write!(f, "#{hash:016x}"),
+            ClassKey::Tuple(parts) => {
+                f.write_str("(")?;
+                write_separated(f, parts)?;
+                f.write_str(")")
+            }
+            ClassKey::Struct(fields) => {
+                f.write_str("{")?;
+                for (i, (name, key)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{name}: {key}")?;
+                }
+                f.write_str("}")
+            }
+            ClassKey::Variant(name, fields) => {
+                f.write_str(name)?;
+                match fields.as_ref() {
+                    ClassKey::Tuple(parts) if parts.is_empty() => Ok(()),
+                    ClassKey::Struct(_) => write!(f, " {fields}"),
+                    _ => write!(f, "{fields}"),
+                }
+            }
+            ClassKey::List { len, items } => {
+                f.write_str("[")?;
+                write_separated(f, items)?;
+                if !items.is_empty() {
+                    f.write_str("; ")?;
+                }
+                match len {
+                    ListLen::Exact(n) => write!(f, "len={n}]"),
+                    ListLen::AtLeast(n) => write!(f, "len>={n}]"),
+                    ListLen::Range(min, end) => write!(f, "len={min}..{end}]"),
+                }
+            }
+        }
+    }
+}
+
+fn write_separated(f: &mut fmt::Formatter<'_>, keys: &[ClassKey]) -> fmt::Result {
+    for (i, key) in keys.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{key}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Classify;
+
+    #[test]
+    fn test_builtin_class_keys() {
+        assert_eq!(0u8.class_key().to_string(), "Zero");
+        assert_eq!((-1.5f64).class_key().to_string(), "Negative");
+        assert_eq!(" ".class_key().to_string(), "Whitespace");
+        assert_eq!(true.class_key().to_string(), "true");
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            chrono::NaiveDate::default().class_key().to_string(),
+            "Default"
+        );
+        assert_eq!(None::<u8>.class_key().to_string(), "None");
+        assert_eq!(Some(1u8).class_key().to_string(), "Some(Positive)");
+        assert_eq!((0u8, "a").class_key().to_string(), "(Zero, NonEmpty)");
+    }
+
+    #[test]
+    fn test_list_class_keys() {
+        assert_eq!(Vec::<u8>::new().class_key().to_string(), "[len=0]");
+        assert_eq!(vec![0u8].class_key().to_string(), "[Zero; len=1]");
+        assert_eq!(
+            vec![1u8, 0, 2].class_key().to_string(),
+            "[Positive, Zero; len>=2]"
+        );
+        assert_eq!(vec![1u8, 0, 2].class_key(), [0u8, 5].class_key());
+    }
+
+    #[test]
+    fn test_class_keys_follow_classify() {
+        let values = [None, Some(None), Some(Some(0i8)), Some(Some(i8::MIN))];
+
+        for a in &values {
+            for b in &values {
+                assert_eq!(a.classify() == b.classify(), a.class_key() == b.class_key());
+            }
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let key = ClassKey::Struct(vec![
+            (
+                "first".to_string(),
+                ClassKey::debug(&crate::IntClassification::Zero),
+            ),
+            ("second".to_string(), ClassKey::unit_variant("B")),
+            (
+                "third".to_string(),
+                ClassKey::Variant(
+                    "C".to_string(),
+                    Box::new(ClassKey::Struct(vec![(
+                        "inner".to_string(),
+                        ClassKey::Opaque(0xff),
+                    )])),
+                ),
+            ),
+        ]);
+
+        assert_eq!(
+            key.to_string(),
+            "{first: Zero, second: B, third: C {inner: #00000000000000ff}}"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{stable_hash, StableHash};
+use std::collections::HashMap;
+
+/// An insertion ordered map keyed by classification.
+///
+/// `Classify::Output` is only required to be `PartialEq + StableHash`, so keys
+/// are bucketed by their hash and compared with `PartialEq` inside each
+/// bucket instead of requiring `Eq`.
+pub(crate) struct ClassMap<K, V> {
+    buckets: HashMap<u64, Vec<usize>>,
+    entries: Vec<(K, V)>,
+}
+
+impl<K: PartialEq + StableHash, V> ClassMap<K, V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            buckets: HashMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn contains_key(&self, key: &K) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Inserts a new key. The caller is expected to have checked that the
+    /// key is not already present.
+    pub(crate) fn insert(&mut self, key: K, value: V) -> &mut V {
+        let index = self.entries.len();
+        self.buckets
+            .entry(stable_hash(&key))
+            .or_default()
+            .push(index);
+        self.entries.push((key, value));
+        &mut self.entries[index].1
+    }
+
+    pub(crate) fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.position(key)?;
+        Some(&mut self.entries[index].1)
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub(crate) fn into_iter(self) -> impl Iterator<Item = (K, V)> {
+        self.entries.into_iter()
+    }
+
+    fn position(&self, key: &K) -> Option<usize> {
+        self.buckets
+            .get(&stable_hash(key))?
+            .iter()
+            .copied()
+            .find(|&index| self.entries[index].0 == *key)
+    }
+}
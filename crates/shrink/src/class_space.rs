@@ -0,0 +1,340 @@
+use crate::stable_hash::StableBytes;
+use crate::{stable_hash, ClassKey, Classify, StableHash, StableHasher};
+use std::hash::Hasher;
+
+/// How many classifications a type can have
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Cardinality {
+    Finite(u128),
+    /// Too many classifications to list, as with slices and `Vec`
+    Unbounded,
+}
+
+impl Cardinality {
+    /// The most classifications that are listed. Products and sums past
+    /// this are [`Cardinality::Unbounded`], since enumerating them would
+    /// never finish. [`Classify::MAX_CLASSES`] is not capped.
+    pub const LIMIT: u128 = 1 << 32;
+
+    /// The number of classifications of a value made of two independent parts
+    pub fn product(self, other: Cardinality) -> Cardinality {
+        match (self, other) {
+            (Cardinality::Finite(a), Cardinality::Finite(b)) => Self::capped(a.checked_mul(b)),
+            _ => Cardinality::Unbounded,
+        }
+    }
+
+    /// The number of classifications of a value that is one of two parts
+    pub fn sum(self, other: Cardinality) -> Cardinality {
+        match (self, other) {
+            (Cardinality::Finite(a), Cardinality::Finite(b)) => Self::capped(a.checked_add(b)),
+            _ => Cardinality::Unbounded,
+        }
+    }
+
+    fn capped(count: Option<u128>) -> Cardinality {
+        match count {
+            Some(count) if count <= Self::LIMIT => Cardinality::Finite(count),
+            _ => Cardinality::Unbounded,
+        }
+    }
+}
+
+/// A type whose possible classifications can be listed.
+///
+/// This makes the reasoning in the readme ("3 * 3 = 9 possible
+/// classifications") available at runtime.
+pub trait ClassSpace: Classify {
+    /// The number of distinct classifications values of this type can have
+    fn cardinality() -> Cardinality;
+
+    /// Every classification values of this type can have. Empty if the
+    /// type is [`Cardinality::Unbounded`], including composite types with
+    /// more than [`Cardinality::LIMIT`] classifications.
+    ///
+    /// Composite classifications are listed lazily, so taking the first few
+    /// of a large space is cheap.
+    fn classes() -> impl Iterator<Item = Self::Output>;
+
+    /// Every classification along with the [`ClassKey`] describing it, in
+    /// the same order as [`ClassSpace::classes`].
+    ///
+    /// Defaults to opaque keys, matching the default
+    /// [`Classify::class_key`].
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| {
+            let key = ClassKey::Opaque(stable_hash(&class));
+            (class, key)
+        })
+    }
+}
+
+/// [`Classify::MAX_CLASSES`] of a value made of two independent parts
+#[doc(hidden)]
+pub const fn max_classes_product(a: Option<u128>, b: Option<u128>) -> Option<u128> {
+    match (a, b) {
+        (Some(a), Some(b)) => a.checked_mul(b),
+        _ => None,
+    }
+}
+
+/// [`Classify::MAX_CLASSES`] of a value that is one of two parts
+#[doc(hidden)]
+pub const fn max_classes_sum(a: Option<u128>, b: Option<u128>) -> Option<u128> {
+    match (a, b) {
+        (Some(a), Some(b)) => a.checked_add(b),
+        _ => None,
+    }
+}
+
+/// Lists every `u64` key of a composite classification, by feeding all
+/// combinations of its parts through a [`StableHasher`] in the same order
+/// as `classify` would. The class keys of the parts are kept alongside, in
+/// the order the parts were added.
+///
+/// Combinations are produced lazily, odometer style: the classes of the
+/// last part added vary fastest, and each part's classes are listed again
+/// for every combination of the parts before it. Only one combination is
+/// held at a time.
+///
+/// Used by tuples and `#[derive(ClassSpace)]`.
+#[doc(hidden)]
+pub struct HashProduct<I> {
+    states: Option<I>,
+    cardinality: Cardinality,
+}
+
+type ProductState = (StableHasher, Vec<ClassKey>);
+
+impl HashProduct<std::iter::Once<ProductState>> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            states: Some(std::iter::once((StableHasher::new(), Vec::new()))),
+            cardinality: Cardinality::Finite(1),
+        }
+    }
+}
+
+impl<I: Iterator<Item = ProductState>> HashProduct<I> {
+    /// Writes the same value into every combination
+    pub fn write<H: StableHash + ?Sized>(
+        self,
+        value: &H,
+    ) -> HashProduct<impl Iterator<Item = ProductState>> {
+        let bytes = StableBytes::of(value);
+        HashProduct {
+            states: self.states.map(|states| {
+                states.map(move |(mut state, keys)| {
+                    state.write(&bytes.0);
+                    (state, keys)
+                })
+            }),
+            cardinality: self.cardinality,
+        }
+    }
+
+    /// Extends every combination with each classification of `T`
+    pub fn classes<T: ClassSpace + ?Sized>(
+        self,
+    ) -> HashProduct<impl Iterator<Item = ProductState>> {
+        let cardinality = self.cardinality.product(T::cardinality());
+        let states = match cardinality {
+            Cardinality::Finite(_) => self.states,
+            Cardinality::Unbounded => None,
+        };
+
+        HashProduct {
+            states: states.map(|states| {
+                states.flat_map(|(state, keys)| {
+                    T::keyed_classes().map(move |(class, key)| {
+                        let mut state = state;
+                        class.stable_hash(&mut state);
+                        let mut keys = keys.clone();
+                        keys.push(key);
+                        (state, keys)
+                    })
+                })
+            }),
+            cardinality,
+        }
+    }
+
+    /// Marks the classification as unbounded, as for fields with a custom
+    /// classifier
+    pub fn unbounded(self) -> Self {
+        Self {
+            states: None,
+            cardinality: Cardinality::Unbounded,
+        }
+    }
+
+    pub fn finish(self) -> impl Iterator<Item = u64> {
+        self.finish_keyed().map(|(class, _)| class)
+    }
+
+    /// Every key along with the class keys of its parts
+    pub fn finish_keyed(self) -> impl Iterator<Item = (u64, Vec<ClassKey>)> {
+        self.states
+            .into_iter()
+            .flatten()
+            .map(|(state, keys)| (state.finish(), keys))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatClassification, IntClassification, StringClassification};
+    use std::collections::HashSet;
+    use std::fmt::Debug;
+    use std::hash::Hash;
+
+    /// Checks that the listed classes are distinct and that their number
+    /// matches the cardinality and `MAX_CLASSES`
+    fn assert_space<T>(expected: u128)
+    where
+        T: ClassSpace,
+        T::Output: Hash + Eq + Debug,
+    {
+        let classes: Vec<T::Output> = T::classes().collect();
+        let unique: HashSet<&T::Output> = classes.iter().collect();
+
+        assert_eq!(T::cardinality(), Cardinality::Finite(expected));
+        assert_eq!(T::MAX_CLASSES, Some(expected));
+        assert_eq!(classes.len() as u128, expected, "{classes:?}");
+        assert_eq!(unique.len(), classes.len(), "{classes:?}");
+    }
+
+    #[test]
+    fn test_builtin_class_spaces() {
+        assert_space::<u8>(3);
+        assert_space::<usize>(3);
+        assert_space::<i64>(5);
+        assert_space::<f32>(7);
+        assert_space::<f64>(7);
+        assert_space::<String>(3);
+        assert_space::<&str>(3);
+        assert_space::<bool>(2);
+        assert_space::<std::time::Duration>(4);
+        assert_space::<std::time::SystemTime>(4);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_class_spaces() {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc};
+
+        assert_space::<NaiveDate>(3);
+        assert_space::<NaiveDateTime>(3);
+        assert_space::<DateTime<Utc>>(3);
+        assert_space::<NaiveTime>(6);
+        assert_space::<TimeDelta>(6);
+    }
+
+    #[test]
+    fn test_classes_are_reachable() {
+        let ints: Vec<_> = u8::classes().collect();
+        assert_eq!(
+            ints,
+            vec![
+                IntClassification::Zero,
+                IntClassification::Positive,
+                IntClassification::Max
+            ]
+        );
+        assert!(f64::classes().any(|c| c == FloatClassification::Subnormal));
+        assert!(String::classes().any(|c| c == StringClassification::Whitespace));
+    }
+
+    #[test]
+    fn test_option_class_space() {
+        assert_space::<Option<u8>>(4);
+        assert_space::<Option<Option<bool>>>(4);
+        assert_eq!(Option::<bool>::classes().next(), Some(None));
+    }
+
+    #[test]
+    fn test_tuple_class_space() {
+        assert_space::<(u8,)>(3);
+        assert_space::<(u8, String)>(9);
+        assert_space::<(u8, String, bool)>(18);
+        assert_space::<(i8, f64, bool, Option<bool>)>(5 * 7 * 2 * 3);
+
+        // The listed keys are exactly the keys values classify to
+        let classes: HashSet<u64> = <(u8, bool)>::classes().collect();
+        for value in [(0u8, true), (1, false), (255, true)] {
+            assert!(classes.contains(&value.classify()));
+        }
+    }
+
+    #[test]
+    fn test_keyed_classes() {
+        let keys: Vec<String> = Option::<bool>::keyed_classes()
+            .map(|(_, key)| key.to_string())
+            .collect();
+        assert_eq!(keys, vec!["None", "Some(false)", "Some(true)"]);
+
+        // Keys describe the class they are listed with
+        for value in [(0u8, None), (7, Some(true))] {
+            let (_, key) = <(u8, Option<bool>)>::keyed_classes()
+                .find(|(class, _)| *class == value.classify())
+                .unwrap();
+            assert_eq!(key, value.class_key());
+        }
+    }
+
+    #[test]
+    fn test_unbounded_class_space() {
+        assert_eq!(Vec::<u8>::cardinality(), Cardinality::Unbounded);
+        assert_eq!(<[bool; 2]>::cardinality(), Cardinality::Unbounded);
+        assert_eq!(<[bool]>::cardinality(), Cardinality::Unbounded);
+        assert_eq!(<(u8, Vec<u8>)>::cardinality(), Cardinality::Unbounded);
+        assert_eq!(<(u8, Vec<u8>)>::classes().count(), 0);
+        assert_eq!(<(u8, Vec<u8>)>::MAX_CLASSES, None);
+    }
+
+    #[test]
+    fn test_cardinality_arithmetic() {
+        use Cardinality::*;
+
+        assert_eq!(Finite(3).product(Finite(4)), Finite(12));
+        assert_eq!(Finite(3).sum(Finite(4)), Finite(7));
+        assert_eq!(Finite(3).product(Unbounded), Unbounded);
+        assert_eq!(Unbounded.sum(Finite(1)), Unbounded);
+        assert_eq!(Finite(u128::MAX).product(Finite(2)), Unbounded);
+
+        assert_eq!(max_classes_product(Some(3), Some(4)), Some(12));
+        assert_eq!(max_classes_sum(Some(3), None), None);
+        assert_eq!(max_classes_product(Some(u128::MAX), Some(2)), None);
+
+        let limit = Cardinality::LIMIT;
+        assert_eq!(Finite(limit).product(Finite(1)), Finite(limit));
+        assert_eq!(Finite(limit).product(Finite(2)), Unbounded);
+        assert_eq!(Finite(limit).sum(Finite(1)), Unbounded);
+        // The bound is exact past the limit, only listing is capped
+        assert_eq!(max_classes_product(Some(limit), Some(2)), Some(limit * 2));
+        assert_eq!(max_classes_sum(Some(limit), Some(1)), Some(limit + 1));
+    }
+
+    #[test]
+    fn test_large_class_spaces_are_lazy() {
+        type Floats = (f64, f64, f64, f64);
+
+        // Millions of classes, of which only the first few are built
+        assert_eq!(
+            <(Floats, Floats)>::cardinality(),
+            Cardinality::Finite(7u128.pow(8))
+        );
+        let first: HashSet<u64> = <(Floats, Floats)>::classes().take(10).collect();
+        assert_eq!(first.len(), 10);
+
+        // Past the limit nothing is listed
+        assert_eq!(
+            <(Floats, Floats, Floats)>::cardinality(),
+            Cardinality::Unbounded
+        );
+        assert_eq!(<(Floats, Floats, Floats)>::MAX_CLASSES, Some(7u128.pow(12)));
+        assert_eq!(<(Floats, Floats, Floats)>::classes().next(), None);
+    }
+}
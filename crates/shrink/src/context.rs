@@ -0,0 +1,298 @@
+use crate::class_key::ListLen;
+use crate::{CivilDate, Clock, DateRange};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// The thresholds used by [`Classify::classify_with`](crate::Classify::classify_with).
+///
+/// [`ClassifyContext::DEFAULT`] holds the thresholds
+/// [`Classify::classify`](crate::Classify::classify) uses. Override some of
+/// them with struct update syntax:
+///
+/// ```
+/// use shrink::{CivilDate, ClassifyContext, DateRange};
+///
+/// let ctx = ClassifyContext {
+///     epoch: CivilDate::new(2000, 1, 1),
+///     date_range: DateRange::years(1900, 2100),
+///     ..ClassifyContext::DEFAULT
+/// };
+/// ```
+///
+/// With the `toml` feature, a context can also be loaded with
+/// `ClassifyContext::from_toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassifyContext {
+    /// The default date. Dates on it are classified as `Default`. Date
+    /// times are only `Default` at midnight.
+    pub epoch: CivilDate,
+    /// The plausible dates. Dates outside of it are classified as
+    /// `ProbablyError`.
+    pub date_range: DateRange,
+    /// Tells a `date_range` relative to today what day it is
+    pub clock: Clock,
+    /// The smallest length in each length bucket of slices, `Vec` and
+    /// arrays, in increasing order starting at zero. The last bucket holds
+    /// every longer length.
+    pub len_buckets: Cow<'static, [usize]>,
+    /// How much detail slices, `Vec` and arrays keep about their items
+    pub granularity: Granularity,
+}
+
+/// How much detail [`ClassifyContext`] asks containers to keep
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Granularity {
+    /// Only which classifications the items have
+    #[default]
+    Coarse,
+    /// Which classifications the items have, and the length bucket of the
+    /// number of items with each
+    Fine,
+}
+
+/// An invalid [`ClassifyContext`] setting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextError {
+    message: String,
+}
+
+impl ClassifyContext {
+    /// The thresholds used by [`Classify::classify`](crate::Classify::classify):
+    /// the epoch is Jan 1st, 1970, dates before it or from 2050 on are
+    /// probably errors, and slices are bucketed by a length of 0, 1 or 2
+    /// and more.
+    pub const DEFAULT: Self = Self {
+        epoch: CivilDate::new(1970, 1, 1),
+        date_range: DateRange::DEFAULT,
+        clock: Clock::System,
+        len_buckets: Cow::Borrowed(&[0, 1, 2]),
+        granularity: Granularity::Coarse,
+    };
+
+    /// Whether `date` is in the plausible `date_range`
+    pub fn in_date_range(&self, date: CivilDate) -> bool {
+        self.date_range.contains(date, &self.clock)
+    }
+
+    /// The index of the length bucket `len` falls in
+    pub fn len_bucket(&self, len: usize) -> usize {
+        self.len_buckets
+            .iter()
+            .rposition(|&min| min <= len)
+            .unwrap_or(0)
+    }
+
+    /// The lengths in the length bucket `len` falls in
+    pub fn len_range(&self, len: usize) -> ListLen {
+        let bucket = self.len_bucket(len);
+        let min = self.len_buckets.get(bucket).copied().unwrap_or(0);
+
+        match self.len_buckets.get(bucket + 1) {
+            Some(&next) if next == min + 1 => ListLen::Exact(min),
+            Some(&next) => ListLen::Range(min, next),
+            None => ListLen::AtLeast(min),
+        }
+    }
+
+    /// Checks that the length buckets start at zero and increase
+    pub fn validate(&self) -> Result<(), ContextError> {
+        if self.len_buckets.first() != Some(&0) {
+            return Err(ContextError::new("len_buckets must start at 0"));
+        }
+        if self.len_buckets.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(ContextError::new("len_buckets must be increasing"));
+        }
+        Ok(())
+    }
+
+    /// Loads a context from TOML. Missing settings keep their default.
+    ///
+    /// ```toml
+    /// epoch = 1970-01-01
+    /// date_range = "1970..2050"
+    /// # Fixes the date relative date ranges are based on
+    /// today = 2024-05-01
+    /// len_buckets = [0, 1, 2]
+    /// granularity = "coarse"
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml(text: &str) -> Result<Self, ContextError> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|err: toml::de::Error| ContextError::new(err.message()))?;
+
+        let mut ctx = Self::DEFAULT;
+        for (key, value) in &table {
+            let invalid = || ContextError::new(format!("invalid value for `{key}`"));
+
+            match key.as_str() {
+                "epoch" => ctx.epoch = toml_date(value).ok_or_else(invalid)?,
+                "date_range" => {
+                    ctx.date_range = value.as_str().ok_or_else(invalid)?.parse()?;
+                }
+                "today" => ctx.clock = Clock::Fixed(toml_date(value).ok_or_else(invalid)?),
+                "len_buckets" => {
+                    let buckets = value.as_array().ok_or_else(invalid)?;
+                    ctx.len_buckets = buckets
+                        .iter()
+                        .map(|min| {
+                            let min = min.as_integer().ok_or_else(invalid)?;
+                            usize::try_from(min).map_err(|_| invalid())
+                        })
+                        .collect::<Result<Vec<_>, _>>()?
+                        .into();
+                }
+                "granularity" => {
+                    ctx.granularity = value.as_str().ok_or_else(invalid)?.parse()?;
+                }
+                _ => return Err(ContextError::new(format!("unknown setting `{key}`"))),
+            }
+        }
+
+        ctx.validate()?;
+        Ok(ctx)
+    }
+}
+
+/// A TOML local date, or a string holding one
+#[cfg(feature = "toml")]
+fn toml_date(value: &toml::Value) -> Option<CivilDate> {
+    match value {
+        toml::Value::Datetime(toml::value::Datetime {
+            date: Some(date),
+            time: None,
+            offset: None,
+        }) => Some(CivilDate::new(
+            date.year.into(),
+            date.month.into(),
+            date.day.into(),
+        )),
+        toml::Value::String(date) => date.parse().ok(),
+        _ => None,
+    }
+}
+
+impl Default for ClassifyContext {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Parses `coarse` or `fine`
+impl FromStr for Granularity {
+    type Err = ContextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "coarse" => Ok(Granularity::Coarse),
+            "fine" => Ok(Granularity::Fine),
+            _ => Err(ContextError::new(format!(
+                "invalid granularity `{s}`, expected `coarse` or `fine`"
+            ))),
+        }
+    }
+}
+
+impl ContextError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ContextError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_len_buckets() {
+        let ctx = ClassifyContext::DEFAULT;
+        assert_eq!(ctx.len_range(0), ListLen::Exact(0));
+        assert_eq!(ctx.len_range(1), ListLen::Exact(1));
+        assert_eq!(ctx.len_range(2), ListLen::AtLeast(2));
+        assert_eq!(ctx.len_range(100), ListLen::AtLeast(2));
+
+        let ctx = ClassifyContext {
+            len_buckets: vec![0, 1, 10, 100].into(),
+            ..ClassifyContext::DEFAULT
+        };
+        assert_eq!(ctx.len_bucket(5), 1);
+        assert_eq!(ctx.len_range(5), ListLen::Range(1, 10));
+        assert_eq!(ctx.len_range(10), ListLen::Range(10, 100));
+        assert_eq!(ctx.len_range(500), ListLen::AtLeast(100));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(ClassifyContext::DEFAULT.validate().is_ok());
+
+        let ctx = ClassifyContext {
+            len_buckets: vec![1, 2].into(),
+            ..ClassifyContext::DEFAULT
+        };
+        assert!(ctx.validate().is_err());
+
+        let ctx = ClassifyContext {
+            len_buckets: vec![0, 5, 5].into(),
+            ..ClassifyContext::DEFAULT
+        };
+        assert!(ctx.validate().is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_toml() {
+        let ctx = ClassifyContext::from_toml(
+            r#"
+            epoch = 2000-01-01
+            date_range = "1900..now+50y"
+            today = 2024-05-01
+            len_buckets = [0, 1, 10]
+            granularity = "fine"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ctx,
+            ClassifyContext {
+                epoch: CivilDate::new(2000, 1, 1),
+                date_range: DateRange {
+                    start: crate::DateBound::Date(CivilDate::new(1900, 1, 1)),
+                    end: crate::DateBound::YearsFromToday(50),
+                },
+                clock: Clock::Fixed(CivilDate::new(2024, 5, 1)),
+                len_buckets: vec![0, 1, 10].into(),
+                granularity: Granularity::Fine,
+            }
+        );
+
+        assert_eq!(
+            ClassifyContext::from_toml(r#"epoch = "1900-06-30""#)
+                .unwrap()
+                .epoch,
+            CivilDate::new(1900, 6, 30)
+        );
+        assert_eq!(
+            ClassifyContext::from_toml("").unwrap(),
+            ClassifyContext::DEFAULT
+        );
+
+        assert!(ClassifyContext::from_toml("date_range = 2100").is_err());
+        assert!(ClassifyContext::from_toml("date_range = \"soon\"").is_err());
+        assert!(ClassifyContext::from_toml("today = 2024-05-01T00:00:00").is_err());
+        assert!(ClassifyContext::from_toml("len_buckets = [0, -1]").is_err());
+        assert!(ClassifyContext::from_toml("len_buckets = [1, 2]").is_err());
+        assert!(ClassifyContext::from_toml("unknown = 1").is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use crate::class_map::ClassMap;
+use crate::{Cardinality, ClassKey, ClassSpace};
+use std::fmt;
+
+/// How a set of items covers the classifications of their type.
+///
+/// Every class listed by [`ClassSpace::keyed_classes`] is included, with
+/// the number of items that landed in it, so classes the items never hit
+/// show up with a count of zero. For unbounded types, and types with more
+/// than [`Coverage::LIMIT`] classes, only the classes that were seen are
+/// listed.
+///
+/// The `Display` impl prints a table of every class and its count.
+#[derive(Debug, Clone)]
+pub struct Coverage<C> {
+    classes: Vec<ClassCount<C>>,
+    cardinality: Cardinality,
+    items: usize,
+    lists_unseen: bool,
+}
+
+/// A single classification of a [`Coverage`] and the number of items in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassCount<C> {
+    pub class: C,
+    pub key: ClassKey,
+    pub count: usize,
+}
+
+impl<C> Coverage<C> {
+    /// The most classes a report lists. Past this the class space is not
+    /// enumerated, and the report is limited to the classes seen.
+    pub const LIMIT: u128 = 1 << 16;
+
+    /// Every class, in the order listed by the class space. Classes seen
+    /// that are not in the class space follow in the order they were seen.
+    pub fn classes(&self) -> &[ClassCount<C>] {
+        &self.classes
+    }
+
+    /// The classes at least one item landed in
+    pub fn seen(&self) -> impl Iterator<Item = &ClassCount<C>> {
+        self.classes.iter().filter(|class| class.count > 0)
+    }
+
+    /// The classes no item landed in. Empty if the type has too many
+    /// classes to list, see [`Coverage::lists_unseen`].
+    pub fn unseen(&self) -> impl Iterator<Item = &ClassCount<C>> {
+        self.classes.iter().filter(|class| class.count == 0)
+    }
+
+    /// Whether every class of the type is listed, rather than only the
+    /// ones seen
+    pub fn lists_unseen(&self) -> bool {
+        self.lists_unseen
+    }
+
+    /// The number of items the report was built from
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    /// The number of classifications the type can have
+    pub fn cardinality(&self) -> Cardinality {
+        self.cardinality
+    }
+
+    /// The share of all classes that were seen, or `None` for unbounded
+    /// types
+    pub fn ratio(&self) -> Option<f64> {
+        match self.cardinality {
+            Cardinality::Finite(0) => Some(1.0),
+            Cardinality::Finite(n) => Some(self.seen().count() as f64 / n as f64),
+            Cardinality::Unbounded => None,
+        }
+    }
+}
+
+impl<C> fmt::Display for Coverage<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<String> = self.classes.iter().map(|c| c.key.to_string()).collect();
+        let key_width = keys.iter().map(String::len).chain([5]).max().unwrap_or(0);
+        let count_width = self
+            .classes
+            .iter()
+            .map(|c| c.count.to_string().len())
+            .chain([5])
+            .max()
+            .unwrap_or(0);
+
+        writeln!(f, "{:<key_width$}  {:>count_width$}", "class", "count")?;
+        for (key, class) in keys.iter().zip(&self.classes) {
+            writeln!(f, "{key:<key_width$}  {:>count_width$}", class.count)?;
+        }
+
+        let seen = self.seen().count();
+        match self.cardinality {
+            Cardinality::Finite(n) if self.lists_unseen => {
+                write!(f, "{seen} of {n} classes seen in {} items", self.items)
+            }
+            Cardinality::Finite(n) => write!(
+                f,
+                "{seen} of {n} classes seen in {} items, too many to list the rest",
+                self.items
+            ),
+            Cardinality::Unbounded => write!(f, "{seen} classes seen in {} items", self.items),
+        }
+    }
+}
+
+/// Builds a [`Coverage`] report of the classes `items` land in, compared
+/// to every class their type can have.
+pub fn coverage<T: ClassSpace, I: IntoIterator<Item = T>>(items: I) -> Coverage<T::Output> {
+    let mut seen = ClassMap::new();
+    let mut total = 0;
+    for item in items {
+        total += 1;
+
+        let class = item.classify();
+        match seen.get_mut(&class) {
+            Some((_, count)) => *count += 1,
+            None => {
+                seen.insert(class, (item.class_key(), 1));
+            }
+        }
+    }
+
+    let cardinality = T::cardinality();
+    let lists_unseen = matches!(
+        cardinality,
+        Cardinality::Finite(n) if n <= Coverage::<T::Output>::LIMIT
+    );
+
+    // Seen classes are moved into the class space order by taking their
+    // count, so the ones left with a count are not in the class space
+    let mut classes = ClassMap::new();
+    if lists_unseen {
+        for (class, key) in T::keyed_classes() {
+            if !classes.contains_key(&class) {
+                let count = seen
+                    .get_mut(&class)
+                    .map_or(0, |(_, count)| std::mem::take(count));
+                classes.insert(class, (key, count));
+            }
+        }
+    }
+    for (class, (key, count)) in seen.into_iter() {
+        if count > 0 {
+            classes.insert(class, (key, count));
+        }
+    }
+
+    Coverage {
+        classes: classes
+            .into_iter()
+            .map(|(class, (key, count))| ClassCount { class, key, count })
+            .collect(),
+        cardinality,
+        items: total,
+        lists_unseen,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntClassification;
+
+    #[test]
+    fn test_coverage_counts() {
+        let report = coverage([1u8, 2, 0, 3]);
+
+        assert_eq!(report.items(), 4);
+        assert_eq!(
+            report.classes(),
+            &[
+                ClassCount {
+                    class: IntClassification::Zero,
+                    key: ClassKey::debug(&IntClassification::Zero),
+                    count: 1,
+                },
+                ClassCount {
+                    class: IntClassification::Positive,
+                    key: ClassKey::debug(&IntClassification::Positive),
+                    count: 3,
+                },
+                ClassCount {
+                    class: IntClassification::Max,
+                    key: ClassKey::debug(&IntClassification::Max),
+                    count: 0,
+                },
+            ]
+        );
+        assert_eq!(
+            report.unseen().map(|c| c.class).collect::<Vec<_>>(),
+            vec![IntClassification::Max]
+        );
+        assert_eq!(report.ratio(), Some(2.0 / 3.0));
+    }
+
+    #[test]
+    fn test_coverage_of_combinations() {
+        let report = coverage([(0u8, true), (0, true), (5, false)]);
+
+        assert_eq!(report.cardinality(), Cardinality::Finite(6));
+        assert_eq!(report.seen().count(), 2);
+        assert!(report.lists_unseen());
+
+        let unseen: Vec<String> = report.unseen().map(|c| c.key.to_string()).collect();
+        assert_eq!(
+            unseen,
+            vec![
+                "(Zero, false)",
+                "(Positive, true)",
+                "(Max, false)",
+                "(Max, true)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coverage_of_unbounded_type() {
+        let report = coverage(vec![vec![0u8], vec![1], vec![]]);
+
+        assert_eq!(report.cardinality(), Cardinality::Unbounded);
+        assert_eq!(report.seen().count(), 3);
+        assert_eq!(report.unseen().count(), 0);
+        assert!(!report.lists_unseen());
+        assert_eq!(report.ratio(), None);
+    }
+
+    #[test]
+    fn test_coverage_of_large_type() {
+        let zero = (0.0f64, 0.0f64, 0.0f64);
+        let half = (0.5f64, 0.5f64, 0.5f64);
+        let report = coverage([(zero, zero), (zero, half), (zero, zero)]);
+
+        assert_eq!(report.cardinality(), Cardinality::Finite(7u128.pow(6)));
+        assert!(!report.lists_unseen());
+        assert_eq!(report.classes().len(), 2);
+        assert_eq!(report.unseen().count(), 0);
+        assert_eq!(report.ratio(), Some(2.0 / 117649.0));
+        assert!(report
+            .to_string()
+            .ends_with("2 of 117649 classes seen in 3 items, too many to list the rest"));
+    }
+
+    #[test]
+    fn test_coverage_table() {
+        let report = coverage([Some(true), Some(true), None]);
+
+        assert_eq!(
+            report.to_string(),
+            "\
+class        count
+None             1
+Some(false)      0
+Some(true)       2
+2 of 3 classes seen in 3 items"
+        );
+    }
+}
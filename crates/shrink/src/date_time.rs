@@ -1,27 +1,53 @@
 use crate::naive_date_time::NaiveDateTimeClassification;
-use crate::Classify;
-use chrono::{DateTime, Datelike, TimeZone, Utc};
+use crate::{Cardinality, CivilDate, ClassKey, ClassSpace, Classify, ClassifyContext};
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeZone};
 
 impl<Tz: TimeZone> Classify for DateTime<Tz> {
     type Output = NaiveDateTimeClassification;
 
+    const MAX_CLASSES: Option<u128> = Some(3);
+
     fn classify(&self) -> Self::Output {
-        let epoch = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
 
-        if self == &epoch {
-            NaiveDateTimeClassification::Default
-        } else if self < &epoch || self.year() >= 2050 {
-            NaiveDateTimeClassification::ProbablyError
-        } else {
-            NaiveDateTimeClassification::Normal
-        }
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let utc = self.naive_utc();
+        NaiveDateTimeClassification::of_zoned(
+            CivilDate::from(utc.date()),
+            utc.time() == NaiveTime::MIN,
+            CivilDate::from(self.date_naive()),
+            ctx,
+        )
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl<Tz: TimeZone> ClassSpace for DateTime<Tz> {
+    fn cardinality() -> Cardinality {
+        NaiveDateTime::cardinality()
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        NaiveDateTime::classes()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        NaiveDateTime::keyed_classes()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{Days, Utc};
+    use chrono::{Days, FixedOffset, Utc};
     use std::ops::{Add, Sub};
 
     fn default() -> DateTime<Utc> {
@@ -66,4 +92,21 @@ mod tests {
             NaiveDateTimeClassification::ProbablyError
         );
     }
+
+    #[test]
+    fn test_offset_date_time_classify() {
+        // 00:30 UTC on Jan 1st, 1970, on Dec 31st, 1969 in local time
+        let west = FixedOffset::west_opt(3600).unwrap();
+        let late = west.with_ymd_and_hms(1969, 12, 31, 23, 30, 0).unwrap();
+        assert_eq!(late.classify(), NaiveDateTimeClassification::Normal);
+
+        // 23:30 UTC on Dec 31st, 1969, on Jan 1st, 1970 in local time
+        let east = FixedOffset::east_opt(3600).unwrap();
+        let early = east.with_ymd_and_hms(1970, 1, 1, 0, 30, 0).unwrap();
+        assert_eq!(early.classify(), NaiveDateTimeClassification::ProbablyError);
+
+        // The epoch at any offset
+        let epoch = west.with_ymd_and_hms(1969, 12, 31, 23, 0, 0).unwrap();
+        assert_eq!(epoch.classify(), NaiveDateTimeClassification::Default);
+    }
 }
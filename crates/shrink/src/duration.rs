@@ -0,0 +1,158 @@
+use crate::{Cardinality, ClassKey, ClassSpace, Classify};
+#[cfg(feature = "chrono")]
+use chrono::TimeDelta;
+use std::time::Duration;
+
+/// The classification of a `std::time::Duration` or a signed duration,
+/// such as `chrono::TimeDelta`. Only signed durations can be `Min` or
+/// `Negative`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DurationClassification {
+    /// The most negative duration
+    Min,
+    /// Any other duration below zero
+    Negative,
+    /// No time at all
+    Zero,
+    /// Any duration shorter than a second
+    SubSecond,
+    /// Any other duration
+    Normal,
+    /// The longest duration
+    Max,
+}
+
+impl Classify for Duration {
+    type Output = DurationClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(4);
+
+    fn classify(&self) -> Self::Output {
+        if self.is_zero() {
+            DurationClassification::Zero
+        } else if *self == Duration::MAX {
+            DurationClassification::Max
+        } else if self.as_secs() == 0 {
+            DurationClassification::SubSecond
+        } else {
+            DurationClassification::Normal
+        }
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+}
+
+impl ClassSpace for Duration {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(4)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        [
+            DurationClassification::Zero,
+            DurationClassification::SubSecond,
+            DurationClassification::Normal,
+            DurationClassification::Max,
+        ]
+        .into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Classify for TimeDelta {
+    type Output = DurationClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(6);
+
+    fn classify(&self) -> Self::Output {
+        if *self == TimeDelta::MIN {
+            DurationClassification::Min
+        } else if *self == TimeDelta::MAX {
+            DurationClassification::Max
+        } else if self.is_zero() {
+            DurationClassification::Zero
+        } else if *self < TimeDelta::zero() {
+            DurationClassification::Negative
+        } else if self.num_seconds() == 0 {
+            DurationClassification::SubSecond
+        } else {
+            DurationClassification::Normal
+        }
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ClassSpace for TimeDelta {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(6)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        [
+            DurationClassification::Min,
+            DurationClassification::Negative,
+            DurationClassification::Zero,
+            DurationClassification::SubSecond,
+            DurationClassification::Normal,
+            DurationClassification::Max,
+        ]
+        .into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_classify() {
+        assert_eq!(Duration::ZERO.classify(), DurationClassification::Zero);
+        assert_eq!(
+            Duration::from_nanos(1).classify(),
+            DurationClassification::SubSecond
+        );
+        assert_eq!(
+            Duration::from_millis(999).classify(),
+            DurationClassification::SubSecond
+        );
+        assert_eq!(
+            Duration::from_secs(1).classify(),
+            DurationClassification::Normal
+        );
+        assert_eq!(Duration::MAX.classify(), DurationClassification::Max);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_time_delta_classify() {
+        assert_eq!(TimeDelta::zero().classify(), DurationClassification::Zero);
+        assert_eq!(
+            TimeDelta::milliseconds(999).classify(),
+            DurationClassification::SubSecond
+        );
+        assert_eq!(
+            TimeDelta::seconds(90).classify(),
+            DurationClassification::Normal
+        );
+        assert_eq!(
+            TimeDelta::milliseconds(-1).classify(),
+            DurationClassification::Negative
+        );
+        assert_eq!(TimeDelta::MIN.classify(), DurationClassification::Min);
+        assert_eq!(TimeDelta::MAX.classify(), DurationClassification::Max);
+    }
+}
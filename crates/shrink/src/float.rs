@@ -1,4 +1,4 @@
-#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FloatClassification {
     /// Represents both positive and negative zero.
     /// In floating-point arithmetic, +0.0 and -0.0 are distinct values,
@@ -40,6 +40,8 @@ macro_rules! impl_classify_for_float {
             impl crate::Classify for $t {
                 type Output = FloatClassification;
 
+                const MAX_CLASSES: Option<u128> = Some(7);
+
                 fn classify(&self) -> FloatClassification {
                     if self.is_nan() {
                         FloatClassification::NaN
@@ -59,6 +61,10 @@ macro_rules! impl_classify_for_float {
                         FloatClassification::Negative
                     }
                 }
+
+                fn class_key(&self) -> crate::ClassKey {
+                    crate::ClassKey::debug(&self.classify())
+                }
             }
         )+
     }
@@ -67,6 +73,38 @@ macro_rules! impl_classify_for_float {
 // Implement for float types
 impl_classify_for_float!(f32, f64);
 
+const FLOAT_CLASSES: [FloatClassification; 7] = [
+    FloatClassification::Zero,
+    FloatClassification::Positive,
+    FloatClassification::Negative,
+    FloatClassification::PositiveInfinity,
+    FloatClassification::NegativeInfinity,
+    FloatClassification::NaN,
+    FloatClassification::Subnormal,
+];
+
+macro_rules! impl_class_space_for_float {
+    ($($t:ty),+) => {
+        $(
+            impl crate::ClassSpace for $t {
+                fn cardinality() -> crate::Cardinality {
+                    crate::Cardinality::Finite(FLOAT_CLASSES.len() as u128)
+                }
+
+                fn classes() -> impl Iterator<Item = FloatClassification> {
+                    FLOAT_CLASSES.into_iter()
+                }
+
+                fn keyed_classes() -> impl Iterator<Item = (FloatClassification, crate::ClassKey)> {
+                    Self::classes().map(|class| (class, crate::ClassKey::debug(&class)))
+                }
+            }
+        )+
+    }
+}
+
+impl_class_space_for_float!(f32, f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
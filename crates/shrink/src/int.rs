@@ -13,6 +13,8 @@ macro_rules! impl_classify_for_uint {
             impl crate::Classify for $t {
                 type Output = IntClassification;
 
+                const MAX_CLASSES: Option<u128> = Some(3);
+
                 fn classify(&self) -> IntClassification {
                     match *self {
                         0 => IntClassification::Zero,
@@ -20,11 +22,17 @@ macro_rules! impl_classify_for_uint {
                         _ => IntClassification::Positive,
                     }
                 }
+
+                fn class_key(&self) -> crate::ClassKey {
+                    crate::ClassKey::debug(&self.classify())
+                }
             }
 
             impl crate::Classify for &$t {
                 type Output = IntClassification;
 
+                const MAX_CLASSES: Option<u128> = Some(3);
+
                 fn classify(&self) -> IntClassification {
                     match *self {
                         0 => IntClassification::Zero,
@@ -32,6 +40,10 @@ macro_rules! impl_classify_for_uint {
                         _ => IntClassification::Positive,
                     }
                 }
+
+                fn class_key(&self) -> crate::ClassKey {
+                    crate::ClassKey::debug(&self.classify())
+                }
             }
         )+
     }
@@ -43,6 +55,8 @@ macro_rules! impl_classify_for_int {
             impl crate::Classify for $t {
                 type Output = IntClassification;
 
+                const MAX_CLASSES: Option<u128> = Some(5);
+
                 fn classify(&self) -> IntClassification {
                     match *self {
                         0 => IntClassification::Zero,
@@ -52,11 +66,17 @@ macro_rules! impl_classify_for_int {
                         _ => IntClassification::Positive,
                     }
                 }
+
+                fn class_key(&self) -> crate::ClassKey {
+                    crate::ClassKey::debug(&self.classify())
+                }
             }
 
             impl crate::Classify for &$t {
                 type Output = IntClassification;
 
+                const MAX_CLASSES: Option<u128> = Some(5);
+
                 fn classify(&self) -> IntClassification {
                     match *self {
                         0 => IntClassification::Zero,
@@ -66,6 +86,10 @@ macro_rules! impl_classify_for_int {
                         _ => IntClassification::Positive,
                     }
                 }
+
+                fn class_key(&self) -> crate::ClassKey {
+                    crate::ClassKey::debug(&self.classify())
+                }
             }
         )+
     }
@@ -73,3 +97,54 @@ macro_rules! impl_classify_for_int {
 
 impl_classify_for_uint!(u8, u16, u32, u64, usize);
 impl_classify_for_int!(i8, i16, i32, i64, isize);
+
+macro_rules! impl_class_space_for_int {
+    ($classes:expr; $($t:ty),+) => {
+        $(
+            impl crate::ClassSpace for $t {
+                fn cardinality() -> crate::Cardinality {
+                    crate::Cardinality::Finite($classes.len() as u128)
+                }
+
+                fn classes() -> impl Iterator<Item = IntClassification> {
+                    $classes.into_iter()
+                }
+
+                fn keyed_classes() -> impl Iterator<Item = (IntClassification, crate::ClassKey)> {
+                    Self::classes().map(|class| (class, crate::ClassKey::debug(&class)))
+                }
+            }
+
+            impl crate::ClassSpace for &$t {
+                fn cardinality() -> crate::Cardinality {
+                    <$t as crate::ClassSpace>::cardinality()
+                }
+
+                fn classes() -> impl Iterator<Item = IntClassification> {
+                    <$t as crate::ClassSpace>::classes()
+                }
+
+                fn keyed_classes() -> impl Iterator<Item = (IntClassification, crate::ClassKey)> {
+                    <$t as crate::ClassSpace>::keyed_classes()
+                }
+            }
+        )+
+    }
+}
+
+const UINT_CLASSES: [IntClassification; 3] = [
+    IntClassification::Zero,
+    IntClassification::Positive,
+    IntClassification::Max,
+];
+
+const INT_CLASSES: [IntClassification; 5] = [
+    IntClassification::Zero,
+    IntClassification::Positive,
+    IntClassification::Negative,
+    IntClassification::Max,
+    IntClassification::Min,
+];
+
+impl_class_space_for_int!(UINT_CLASSES; u8, u16, u32, u64, usize);
+impl_class_space_for_int!(INT_CLASSES; i8, i16, i32, i64, isize);
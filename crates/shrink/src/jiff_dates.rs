@@ -0,0 +1,332 @@
+use crate::{
+    CalendarClassification, CalendarClassify, CalendarZone, Cardinality, CivilDate, ClassKey,
+    ClassSpace, Classify, ClassifyContext, NaiveDateClassification, NaiveDateTimeClassification,
+};
+use jiff::civil::{Date, DateTime, Time};
+use jiff::tz::Offset;
+use jiff::{SignedDuration, Timestamp, Zoned};
+
+impl From<Date> for CivilDate {
+    fn from(date: Date) -> Self {
+        CivilDate::new(date.year().into(), date.month() as u32, date.day() as u32)
+    }
+}
+
+impl Classify for Date {
+    type Output = NaiveDateClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        NaiveDateClassification::of(CivilDate::from(*self), ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl ClassSpace for Date {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(3)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        NaiveDateClassification::ALL.into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+impl Classify for DateTime {
+    type Output = NaiveDateTimeClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let date = CivilDate::from(self.date());
+        NaiveDateTimeClassification::of(date, self.time() == Time::midnight(), ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl ClassSpace for DateTime {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(3)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        NaiveDateTimeClassification::ALL.into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+/// Classifies an instant whose local date time is `local`
+fn classify_instant(
+    timestamp: Timestamp,
+    local: DateTime,
+    ctx: &ClassifyContext,
+) -> NaiveDateTimeClassification {
+    let utc = Offset::UTC.to_datetime(timestamp);
+    NaiveDateTimeClassification::of_zoned(
+        CivilDate::from(utc.date()),
+        utc.time() == Time::midnight(),
+        CivilDate::from(local.date()),
+        ctx,
+    )
+}
+
+impl Classify for Zoned {
+    type Output = NaiveDateTimeClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        classify_instant(self.timestamp(), self.datetime(), ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl ClassSpace for Zoned {
+    fn cardinality() -> Cardinality {
+        DateTime::cardinality()
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        DateTime::classes()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        DateTime::keyed_classes()
+    }
+}
+
+/// A timestamp is classified as a date time in UTC
+impl Classify for Timestamp {
+    type Output = NaiveDateTimeClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        classify_instant(*self, Offset::UTC.to_datetime(*self), ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl ClassSpace for Timestamp {
+    fn cardinality() -> Cardinality {
+        DateTime::cardinality()
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        DateTime::classes()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        DateTime::keyed_classes()
+    }
+}
+
+impl CalendarClassify for Date {
+    const ZONES: &'static [CalendarZone] = &[CalendarZone::Naive];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        CalendarClassification::of(CivilDate::from(*self), CalendarZone::Naive)
+    }
+}
+
+impl CalendarClassify for DateTime {
+    const ZONES: &'static [CalendarZone] = &[CalendarZone::Naive];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        self.date().classify_calendar()
+    }
+}
+
+impl CalendarClassify for Timestamp {
+    const ZONES: &'static [CalendarZone] = &[CalendarZone::Utc];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        let date = Offset::UTC.to_datetime(*self).date();
+        CalendarClassification::of(CivilDate::from(date), CalendarZone::Utc)
+    }
+}
+
+impl CalendarClassify for Zoned {
+    const ZONES: &'static [CalendarZone] = &[
+        CalendarZone::Utc,
+        CalendarZone::Offset,
+        CalendarZone::DstTransition,
+    ];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        let offset = self.offset();
+        let timestamp = self.timestamp();
+
+        let hour = SignedDuration::from_hours(1);
+        let changes = [timestamp.checked_sub(hour), timestamp.checked_add(hour)]
+            .into_iter()
+            .flatten()
+            .any(|other| self.time_zone().to_offset(other) != offset);
+
+        let zone = if changes {
+            CalendarZone::DstTransition
+        } else if offset == Offset::UTC {
+            CalendarZone::Utc
+        } else {
+            CalendarZone::Offset
+        };
+
+        CalendarClassification::of(CivilDate::from(self.date()), zone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Calendar, CalendarDay, DateRange};
+    use jiff::civil::date;
+    use jiff::tz::TimeZone;
+
+    #[test]
+    fn test_date_classify() {
+        assert_eq!(
+            date(1970, 1, 1).classify(),
+            NaiveDateClassification::Default
+        );
+        assert_eq!(
+            date(2023, 7, 26).classify(),
+            NaiveDateClassification::Normal
+        );
+        assert_eq!(
+            date(1969, 12, 31).classify(),
+            NaiveDateClassification::ProbablyError
+        );
+        assert_eq!(
+            date(2050, 1, 1).classify(),
+            NaiveDateClassification::ProbablyError
+        );
+
+        let ctx = ClassifyContext {
+            date_range: DateRange::years(1900, 2100),
+            ..ClassifyContext::DEFAULT
+        };
+        assert_eq!(
+            date(1955, 4, 2).classify_with(&ctx),
+            NaiveDateClassification::Normal
+        );
+    }
+
+    #[test]
+    fn test_date_time_classify() {
+        let epoch = date(1970, 1, 1).at(0, 0, 0, 0);
+        assert_eq!(epoch.classify(), NaiveDateTimeClassification::Default);
+        assert_eq!(
+            date(1970, 1, 1).at(0, 0, 1, 0).classify(),
+            NaiveDateTimeClassification::Normal
+        );
+        assert_eq!(
+            Timestamp::UNIX_EPOCH.classify(),
+            NaiveDateTimeClassification::Default
+        );
+        assert_eq!(
+            Timestamp::now().classify(),
+            NaiveDateTimeClassification::Normal
+        );
+
+        // Midnight in UTC is still the epoch in another zone, and the
+        // instant decides whether it is before the range
+        let west = TimeZone::fixed(Offset::constant(-1));
+        let east = TimeZone::fixed(Offset::constant(1));
+        assert_eq!(
+            Timestamp::UNIX_EPOCH.to_zoned(west.clone()).classify(),
+            NaiveDateTimeClassification::Default
+        );
+        assert_eq!(
+            Timestamp::from_second(1800)
+                .unwrap()
+                .to_zoned(west)
+                .classify(),
+            NaiveDateTimeClassification::Normal
+        );
+        assert_eq!(
+            Timestamp::from_second(-1800)
+                .unwrap()
+                .to_zoned(east)
+                .classify(),
+            NaiveDateTimeClassification::ProbablyError
+        );
+    }
+
+    #[test]
+    fn test_calendar_classify() {
+        assert_eq!(
+            Calendar(date(2024, 2, 29)).classify().day,
+            CalendarDay::LeapDay
+        );
+
+        let utc = Timestamp::UNIX_EPOCH.to_zoned(TimeZone::UTC);
+        assert_eq!(Calendar(&utc).classify().zone, CalendarZone::Utc);
+
+        // UTC+1 in winter and UTC+2 in summer, changing at 01:00 UTC on the
+        // last Sundays of March and October
+        let cet = TimeZone::posix("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        let zone = |hour, minute| {
+            let utc = date(2024, 3, 31).at(hour, minute, 0, 0);
+            let zoned = TimeZone::UTC
+                .to_zoned(utc)
+                .unwrap()
+                .with_time_zone(cet.clone());
+            Calendar(zoned).classify().zone
+        };
+        assert_eq!(zone(0, 0), CalendarZone::DstTransition);
+        assert_eq!(zone(1, 30), CalendarZone::DstTransition);
+        assert_eq!(zone(2, 1), CalendarZone::Offset);
+    }
+}
@@ -2,27 +2,112 @@ extern crate core;
 
 mod arrays_and_slices;
 mod bool;
+mod calendar;
+mod civil_date;
+mod class_key;
+mod class_map;
+mod class_space;
+mod context;
+mod coverage;
+#[cfg(feature = "chrono")]
 mod date_time;
+mod duration;
 mod float;
 mod int;
+#[cfg(feature = "jiff")]
+mod jiff_dates;
 mod naive_date;
 mod naive_date_time;
+#[cfg(feature = "chrono")]
+mod naive_time;
 mod option;
+#[cfg(feature = "rayon")]
+mod par;
+mod shrink_iter;
+mod shrinker;
+mod stable_hash;
+mod strategy;
 mod string;
+mod system_time;
+#[cfg(feature = "time")]
+mod time_dates;
 mod tuple;
 
 use std::hash::Hash;
 
+pub use calendar::{
+    Calendar, CalendarClassification, CalendarClassify, CalendarDay, CalendarWeek, CalendarZone,
+};
+pub use civil_date::{CivilDate, Clock, DateBound, DateRange};
+pub use class_key::{ClassKey, ListLen};
+#[doc(hidden)]
+pub use class_space::{max_classes_product, max_classes_sum, HashProduct};
+pub use class_space::{Cardinality, ClassSpace};
+pub use context::{ClassifyContext, ContextError, Granularity};
+pub use coverage::{coverage, ClassCount, Coverage};
+pub use duration::DurationClassification;
+pub use float::FloatClassification;
+pub use int::IntClassification;
+pub use naive_date::NaiveDateClassification;
+pub use naive_date_time::NaiveDateTimeClassification;
+#[cfg(feature = "chrono")]
+pub use naive_time::NaiveTimeClassification;
+pub use string::StringClassification;
+pub use system_time::SystemTimeClassification;
+
+#[cfg(feature = "rayon")]
+pub use par::ParShrinkExt;
+pub use shrink_iter::{ShrinkByClass, ShrinkExt};
+pub use shrinker::{
+    shrink, shrink_with, shrink_with_stats, sort_by_rarity, ClassStats, Shrinker, ShrunkClass,
+};
+pub use stable_hash::{stable_hash, StableHash, StableHasher};
+pub use strategy::{
+    Distance, KeepCustom, KeepDiverse, KeepFirst, KeepLargest, KeepLast, KeepRandom, KeepSmallest,
+    Strategy,
+};
+
 pub trait Classify {
-    type Output: PartialEq + Hash;
+    type Output: PartialEq + Hash + StableHash;
+
+    /// An upper bound on the number of distinct classifications, or `None`
+    /// if there is no fixed bound, as with slices and `Vec`.
+    const MAX_CLASSES: Option<u128> = None;
 
     fn classify(&self) -> Self::Output;
+
+    /// Classifies with the thresholds in `ctx` in place of the defaults.
+    ///
+    /// Types without thresholds ignore `ctx`, which is the default. The
+    /// date, slice and derived impls use it, and pass it on to the values
+    /// they contain.
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let _ = ctx;
+        self.classify()
+    }
+
+    /// A structured, printable form of this value's classification.
+    ///
+    /// Defaults to the stable hash of [`Classify::classify`]. The built-in
+    /// and derived impls describe the classification instead.
+    fn class_key(&self) -> ClassKey {
+        ClassKey::Opaque(stable_hash(&self.classify()))
+    }
+
+    /// The class key of [`Classify::classify_with`]. Impls that override
+    /// `classify_with` should override this too.
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        let _ = ctx;
+        self.class_key()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::int::IntClassification;
     use crate::Classify;
+    use std::fmt::Debug;
+    use std::hash::Hash;
 
     #[test]
     fn classify_u8() {
@@ -30,4 +115,86 @@ mod tests {
         assert_eq!(1u8.classify(), IntClassification::Positive);
         assert_eq!(u8::MAX.classify(), IntClassification::Max);
     }
+
+    /// Checks that `T` can be classified inside every container impl, given
+    /// two values `a` and `b` with different classifications
+    fn assert_classifies_in_containers<T>(a: T, b: T)
+    where
+        T: Classify + Clone,
+        T::Output: Hash + Eq + Ord + Debug,
+    {
+        assert_ne!(a.classify(), b.classify());
+
+        // Vec
+        assert_eq!(
+            vec![a.clone(), b.clone()].classify(),
+            vec![b.clone(), a.clone()].classify()
+        );
+        assert_ne!(
+            vec![a.clone(), a.clone()].classify(),
+            vec![a.clone(), b.clone()].classify()
+        );
+
+        // Array
+        assert_eq!(
+            [a.clone(), b.clone()].classify(),
+            vec![b.clone(), a.clone()].classify()
+        );
+        assert_ne!([a.clone()].classify(), [b.clone()].classify());
+
+        // Slice
+        let items = [a.clone(), b.clone()];
+        assert_eq!(items[..].classify(), items.classify());
+        assert_ne!(items[..1].classify(), items[1..].classify());
+
+        // Option
+        assert_ne!(Some(a.clone()).classify(), Some(b.clone()).classify());
+        assert_ne!(None::<T>.classify(), Some(a.clone()).classify());
+        assert_ne!(
+            vec![Some(a.clone())].classify(),
+            vec![None, Some(a.clone())].classify()
+        );
+
+        // Tuple
+        assert_ne!((a.clone(), 0u8).classify(), (b.clone(), 0u8).classify());
+        assert_ne!(
+            vec![(a.clone(), true)].classify(),
+            vec![(b.clone(), true)].classify()
+        );
+    }
+
+    #[test]
+    fn classify_primitives_in_containers() {
+        assert_classifies_in_containers(1u8, 0);
+        assert_classifies_in_containers(1usize, usize::MAX);
+        assert_classifies_in_containers(-1i64, 0);
+        assert_classifies_in_containers(i32::MIN, 1);
+        assert_classifies_in_containers(1.0f32, f32::NAN);
+        assert_classifies_in_containers(1.0f64, f64::NEG_INFINITY);
+        assert_classifies_in_containers("a", "");
+        assert_classifies_in_containers(String::from(" "), String::from("a"));
+        assert_classifies_in_containers(true, false);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn classify_chrono_dates_in_containers() {
+        use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+        assert_classifies_in_containers(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            NaiveDate::default(),
+        );
+        assert_classifies_in_containers(
+            NaiveDateTime::default(),
+            NaiveDate::from_ymd_opt(1960, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        );
+        assert_classifies_in_containers(
+            Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap(),
+        );
+    }
 }
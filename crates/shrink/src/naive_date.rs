@@ -1,26 +1,35 @@
-use crate::Classify;
+#[cfg(feature = "chrono")]
+use crate::{Cardinality, ClassKey, ClassSpace, Classify};
+use crate::{CivilDate, ClassifyContext};
+#[cfg(feature = "chrono")]
 use chrono::{Datelike, NaiveDate};
 
-#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum NaiveDateClassification {
     /// Any date prior to the epoch, Jan 1st, 1970
     /// or after Jan 1st, 2050
     ProbablyError,
     /// The default date, epoch Jan 1st, 1970
+    ///
+    /// The epoch and the range of plausible dates can be changed with a
+    /// [`ClassifyContext`].
     Default,
     /// Any date other than the epoch
     Normal,
 }
 
-impl Classify for NaiveDate {
-    type Output = NaiveDateClassification;
-
-    fn classify(&self) -> Self::Output {
-        let epoch = NaiveDate::default();
+#[cfg_attr(
+    not(any(feature = "chrono", feature = "time", feature = "jiff")),
+    allow(dead_code)
+)]
+impl NaiveDateClassification {
+    pub(crate) const ALL: [Self; 3] = [Self::ProbablyError, Self::Default, Self::Normal];
 
-        if self == &epoch {
+    /// Classifies a date, whichever date library it comes from
+    pub(crate) fn of(date: CivilDate, ctx: &ClassifyContext) -> Self {
+        if date == ctx.epoch {
             NaiveDateClassification::Default
-        } else if self < &epoch || self.year() >= 2050 {
+        } else if !ctx.in_date_range(date) {
             NaiveDateClassification::ProbablyError
         } else {
             NaiveDateClassification::Normal
@@ -28,9 +37,55 @@ impl Classify for NaiveDate {
     }
 }
 
-#[cfg(test)]
+#[cfg(feature = "chrono")]
+impl Classify for NaiveDate {
+    type Output = NaiveDateClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        NaiveDateClassification::of(CivilDate::from(*self), ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<NaiveDate> for CivilDate {
+    fn from(date: NaiveDate) -> Self {
+        CivilDate::new(date.year(), date.month(), date.day())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ClassSpace for NaiveDate {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(3)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        NaiveDateClassification::ALL.into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use super::*;
+    use crate::{Clock, DateRange};
     use chrono::NaiveDate;
 
     #[test]
@@ -62,4 +117,59 @@ mod tests {
         let far_past = NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
         assert_eq!(far_past.classify(), NaiveDateClassification::ProbablyError);
     }
+
+    #[test]
+    fn test_naive_date_classify_with() {
+        let ctx = ClassifyContext {
+            epoch: CivilDate::new(1900, 1, 1),
+            date_range: DateRange::years(1900, 2100),
+            ..ClassifyContext::DEFAULT
+        };
+
+        let birthday = NaiveDate::from_ymd_opt(1955, 4, 2).unwrap();
+        assert_eq!(
+            birthday.classify_with(&ctx),
+            NaiveDateClassification::Normal
+        );
+
+        let maturity = NaiveDate::from_ymd_opt(2070, 6, 30).unwrap();
+        assert_eq!(
+            maturity.classify_with(&ctx),
+            NaiveDateClassification::Normal
+        );
+
+        let epoch = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+        assert_eq!(epoch.classify_with(&ctx), NaiveDateClassification::Default);
+        assert_eq!(
+            NaiveDate::default().classify_with(&ctx),
+            NaiveDateClassification::Normal
+        );
+    }
+
+    #[test]
+    fn test_naive_date_relative_range() {
+        let ctx = ClassifyContext {
+            date_range: DateRange::relative(100, 50),
+            clock: Clock::Fixed(CivilDate::new(2024, 5, 1)),
+            ..ClassifyContext::DEFAULT
+        };
+
+        let birthday = NaiveDate::from_ymd_opt(1930, 1, 1).unwrap();
+        assert_eq!(
+            birthday.classify_with(&ctx),
+            NaiveDateClassification::Normal
+        );
+
+        let too_old = NaiveDate::from_ymd_opt(1924, 4, 30).unwrap();
+        assert_eq!(
+            too_old.classify_with(&ctx),
+            NaiveDateClassification::ProbablyError
+        );
+
+        let maturity = NaiveDate::from_ymd_opt(2074, 4, 30).unwrap();
+        assert_eq!(
+            maturity.classify_with(&ctx),
+            NaiveDateClassification::Normal
+        );
+    }
 }
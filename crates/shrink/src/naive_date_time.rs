@@ -1,7 +1,10 @@
-use crate::Classify;
-use chrono::{Datelike, NaiveDateTime};
+#[cfg(feature = "chrono")]
+use crate::{Cardinality, ClassKey, ClassSpace, Classify};
+use crate::{CivilDate, ClassifyContext};
+#[cfg(feature = "chrono")]
+use chrono::{NaiveDateTime, NaiveTime};
 
-#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum NaiveDateTimeClassification {
     /// Any date prior to the epoch, Jan 1st, 1970
     /// or after Jan 1st, 2050
@@ -12,15 +15,35 @@ pub enum NaiveDateTimeClassification {
     Normal,
 }
 
-impl Classify for NaiveDateTime {
-    type Output = NaiveDateTimeClassification;
+#[cfg_attr(
+    not(any(feature = "chrono", feature = "time", feature = "jiff")),
+    allow(dead_code)
+)]
+impl NaiveDateTimeClassification {
+    pub(crate) const ALL: [Self; 3] = [Self::ProbablyError, Self::Default, Self::Normal];
 
-    fn classify(&self) -> Self::Output {
-        let epoch = NaiveDateTime::default();
+    /// Classifies a date time, whichever date library it comes from, by
+    /// its date and whether it is at midnight
+    pub(crate) fn of(date: CivilDate, midnight: bool, ctx: &ClassifyContext) -> Self {
+        Self::of_zoned(date, midnight, date, ctx)
+    }
 
-        if self == &epoch {
+    /// Classifies a date time with a time zone. The epoch and the start of
+    /// the range are compared with the instant, in UTC, so anything before
+    /// midnight UTC on the first day of the range is too early whatever
+    /// its offset. The end of the range holds local dates.
+    pub(crate) fn of_zoned(
+        utc_date: CivilDate,
+        utc_midnight: bool,
+        local_date: CivilDate,
+        ctx: &ClassifyContext,
+    ) -> Self {
+        let range = &ctx.date_range;
+        if utc_date == ctx.epoch && utc_midnight {
             NaiveDateTimeClassification::Default
-        } else if self < &epoch || self.year() >= 2050 {
+        } else if utc_date < range.start.resolve(&ctx.clock)
+            || local_date >= range.end.resolve(&ctx.clock)
+        {
             NaiveDateTimeClassification::ProbablyError
         } else {
             NaiveDateTimeClassification::Normal
@@ -28,7 +51,46 @@ impl Classify for NaiveDateTime {
     }
 }
 
-#[cfg(test)]
+#[cfg(feature = "chrono")]
+impl Classify for NaiveDateTime {
+    type Output = NaiveDateTimeClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let date = CivilDate::from(self.date());
+        NaiveDateTimeClassification::of(date, self.time() == NaiveTime::MIN, ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ClassSpace for NaiveDateTime {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(3)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        NaiveDateTimeClassification::ALL.into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use super::*;
     use chrono::{Days, NaiveDateTime, Utc};
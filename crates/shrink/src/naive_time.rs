@@ -0,0 +1,108 @@
+use crate::{Cardinality, ClassKey, ClassSpace, Classify};
+use chrono::{NaiveTime, Timelike};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NaiveTimeClassification {
+    /// 00:00:00, the default time
+    Midnight,
+    /// 12:00:00
+    Noon,
+    /// Any time within the last second of the day, 23:59:59
+    EndOfDay,
+    /// A leap second, such as 23:59:60
+    LeapSecond,
+    /// Any other time with a fraction of a second
+    SubSecond,
+    /// Any other time
+    Normal,
+}
+
+impl Classify for NaiveTime {
+    type Output = NaiveTimeClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(6);
+
+    fn classify(&self) -> Self::Output {
+        if self.nanosecond() >= 1_000_000_000 {
+            NaiveTimeClassification::LeapSecond
+        } else if *self == NaiveTime::MIN {
+            NaiveTimeClassification::Midnight
+        } else if (self.hour(), self.minute(), self.second()) == (23, 59, 59) {
+            NaiveTimeClassification::EndOfDay
+        } else if self.nanosecond() != 0 {
+            NaiveTimeClassification::SubSecond
+        } else if (self.hour(), self.minute(), self.second()) == (12, 0, 0) {
+            NaiveTimeClassification::Noon
+        } else {
+            NaiveTimeClassification::Normal
+        }
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+}
+
+impl ClassSpace for NaiveTime {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(6)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        [
+            NaiveTimeClassification::Midnight,
+            NaiveTimeClassification::Noon,
+            NaiveTimeClassification::EndOfDay,
+            NaiveTimeClassification::LeapSecond,
+            NaiveTimeClassification::SubSecond,
+            NaiveTimeClassification::Normal,
+        ]
+        .into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32, second: u32, nano: u32) -> NaiveTime {
+        NaiveTime::from_hms_nano_opt(hour, minute, second, nano).unwrap()
+    }
+
+    #[test]
+    fn test_naive_time_classify() {
+        assert_eq!(
+            NaiveTime::default().classify(),
+            NaiveTimeClassification::Midnight
+        );
+        assert_eq!(time(12, 0, 0, 0).classify(), NaiveTimeClassification::Noon);
+        assert_eq!(
+            time(23, 59, 59, 0).classify(),
+            NaiveTimeClassification::EndOfDay
+        );
+        assert_eq!(
+            time(23, 59, 59, 999_999_999).classify(),
+            NaiveTimeClassification::EndOfDay
+        );
+        assert_eq!(
+            time(23, 59, 59, 1_500_000_000).classify(),
+            NaiveTimeClassification::LeapSecond
+        );
+        assert_eq!(
+            time(0, 0, 0, 1).classify(),
+            NaiveTimeClassification::SubSecond
+        );
+        assert_eq!(
+            time(12, 0, 0, 500).classify(),
+            NaiveTimeClassification::SubSecond
+        );
+        assert_eq!(
+            time(9, 30, 0, 0).classify(),
+            NaiveTimeClassification::Normal
+        );
+    }
+}
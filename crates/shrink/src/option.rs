@@ -1,11 +1,51 @@
-use crate::Classify;
+use crate::class_space::max_classes_sum;
+use crate::{Cardinality, ClassKey, ClassSpace, Classify, ClassifyContext};
 
 impl<T: Classify> Classify for Option<T> {
     type Output = Option<T::Output>;
 
+    const MAX_CLASSES: Option<u128> = max_classes_sum(Some(1), T::MAX_CLASSES);
+
     fn classify(&self) -> Self::Output {
         self.as_ref().map(|x| x.classify())
     }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        self.as_ref().map(|x| x.classify_with(ctx))
+    }
+
+    fn class_key(&self) -> ClassKey {
+        self.class_key_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        match self {
+            None => ClassKey::unit_variant("None"),
+            Some(x) => ClassKey::Variant(
+                "Some".to_string(),
+                Box::new(ClassKey::Tuple(vec![x.class_key_with(ctx)])),
+            ),
+        }
+    }
+}
+
+impl<T: ClassSpace> ClassSpace for Option<T> {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(1).sum(T::cardinality())
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        std::iter::once(None).chain(T::classes().map(Some))
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        let some = T::keyed_classes().map(|(class, key)| {
+            let key = ClassKey::Variant("Some".to_string(), Box::new(ClassKey::Tuple(vec![key])));
+            (Some(class), key)
+        });
+
+        std::iter::once((None, ClassKey::unit_variant("None"))).chain(some)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,128 @@
+use crate::strategy::Strategy;
+use crate::{Classify, Shrinker};
+use rayon::iter::ParallelIterator;
+
+/// Extension trait adding shrinking to rayon's parallel iterators.
+///
+/// Each rayon job shrinks its part of the input with its own [`Shrinker`],
+/// and the shrinkers are combined with [`Shrinker::merge`]. For indexed
+/// iterators, such as those over a `Vec` or a slice, parts are merged in
+/// input order, so the result is the same as shrinking sequentially.
+pub trait ParShrinkExt: ParallelIterator
+where
+    Self::Item: Classify,
+    <Self::Item as Classify>::Output: Send,
+{
+    /// Reduces the items to one representative per classification, the
+    /// parallel form of [`crate::shrink`].
+    fn par_shrink(self) -> Vec<Self::Item> {
+        self.par_shrinker(Shrinker::new).into_representatives()
+    }
+
+    /// Shrinks the items with shrinkers made by `new`, returning the merged
+    /// shrinker along with its stats.
+    ///
+    /// `new` is called once per rayon job. Strategies with a seed, such as
+    /// [`KeepRandom`](crate::KeepRandom), pick different items depending on
+    /// how the input was split, which depends on the number of threads and
+    /// on work stealing, so they are not reproducible from run to run. The
+    /// split is fixed when `with_min_len` and `with_max_len` are given the
+    /// same length and the input is that length times a power of two.
+    fn par_shrinker<S, F>(self, new: F) -> Shrinker<Self::Item, S>
+    where
+        S: Strategy<Self::Item> + Send,
+        F: Fn() -> Shrinker<Self::Item, S> + Sync + Send,
+    {
+        self.fold(&new, |mut shrinker, item| {
+            shrinker.push(item);
+            shrinker
+        })
+        .reduce(&new, |mut left, right| {
+            left.merge(right);
+            left
+        })
+    }
+}
+
+impl<I: ParallelIterator> ParShrinkExt for I
+where
+    I::Item: Classify,
+    <I::Item as Classify>::Output: Send,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shrink, KeepRandom};
+    use rayon::prelude::*;
+    use rayon::ThreadPoolBuilder;
+
+    #[test]
+    fn test_par_shrink_matches_shrink() {
+        let items: Vec<i32> = (-1000..1000).chain([i32::MAX, 0, i32::MIN]).collect();
+
+        assert_eq!(items.clone().into_par_iter().par_shrink(), shrink(items));
+    }
+
+    #[test]
+    fn test_par_shrinker_stats() {
+        let items: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+
+        let shrinker = items.par_iter().copied().par_shrinker(Shrinker::new);
+        let sequential: Shrinker<u8> = items.into_iter().collect();
+
+        assert_eq!(shrinker.total(), 10_000);
+        assert_eq!(shrinker.into_classes(), sequential.into_classes());
+    }
+
+    fn seeded() -> Shrinker<u32, KeepRandom> {
+        Shrinker::with_strategy(KeepRandom::with_seed(7)).per_class(3)
+    }
+
+    /// What `par_shrinker` does over `parts`: every part is folded into a
+    /// fresh shrinker, merged into another fresh one, and the results are
+    /// merged in halves
+    fn fold_and_merge(parts: &[&[u32]]) -> Shrinker<u32, KeepRandom> {
+        if let [part] = parts {
+            let mut folded = seeded();
+            folded.extend(part.iter().copied());
+            let mut reduced = seeded();
+            reduced.merge(folded);
+            return reduced;
+        }
+
+        let (left, right) = parts.split_at(parts.len() / 2);
+        let mut left = fold_and_merge(left);
+        left.merge(fold_and_merge(right));
+        left
+    }
+
+    #[test]
+    fn test_par_shrinker_with_strategy() {
+        const PART: usize = 512;
+        let items: Vec<u32> = (0..8 * PART as u32).collect();
+        let parts: Vec<&[u32]> = items.chunks(PART).collect();
+        let expected = fold_and_merge(&parts).into_classes();
+
+        for threads in [1, 2, 3, 8] {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let shrinker = pool.install(|| {
+                items
+                    .par_iter()
+                    .copied()
+                    .with_min_len(PART)
+                    .with_max_len(PART)
+                    .par_shrinker(seeded)
+            });
+
+            // Zero and the other positive numbers
+            assert_eq!(shrinker.len(), 2);
+            assert_eq!(shrinker.representatives().count(), 4);
+            assert_eq!(shrinker.into_classes(), expected, "{threads} threads");
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use crate::class_map::ClassMap;
+use crate::strategy::Distance;
+use crate::Classify;
+use std::iter::FusedIterator;
+
+/// Extension trait adding lazy shrinking to any iterator of classifiable items
+pub trait ShrinkExt: Iterator + Sized
+where
+    Self::Item: Classify,
+{
+    /// Lazily yields only the first item of each new classification.
+    ///
+    /// Unlike [`crate::shrink`], the input is never collected, so only the
+    /// classifications seen so far are held in memory.
+    fn shrink_by_class(self) -> ShrinkByClass<Self> {
+        ShrinkByClass {
+            iter: self,
+            classes: ClassMap::new(),
+            per_class: 1,
+            key: None,
+            distinct: |_, _| true,
+            seen: 0,
+            dropped: 0,
+        }
+    }
+}
+
+impl<I: Iterator> ShrinkExt for I where I::Item: Classify {}
+
+/// Iterator returned by [`ShrinkExt::shrink_by_class`]
+pub struct ShrinkByClass<I: Iterator, K = (), F = fn(&<I as Iterator>::Item) -> K>
+where
+    I::Item: Classify,
+{
+    iter: I,
+    /// The number of items yielded for each classification, and their keys
+    classes: ClassMap<<I::Item as Classify>::Output, (usize, Vec<K>)>,
+    per_class: usize,
+    key: Option<F>,
+    distinct: fn(&[K], &K) -> bool,
+    seen: usize,
+    dropped: usize,
+}
+
+impl<I: Iterator, K, F: FnMut(&I::Item) -> K> ShrinkByClass<I, K, F>
+where
+    I::Item: Classify,
+{
+    /// Yields up to `k` items of each classification.
+    ///
+    /// Only the number of items yielded per classification is stored, or
+    /// their keys with [`ShrinkByClass::diverse_by_key`], never the items.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is zero.
+    pub fn per_class(mut self, k: usize) -> Self {
+        assert!(
+            k > 0,
+            "shrink_by_class must yield at least one item per class"
+        );
+        self.per_class = k;
+        self
+    }
+
+    /// Only yields an item if its key differs from the keys of the items
+    /// already yielded for its classification.
+    ///
+    /// Items are yielded as soon as they are seen, so unlike
+    /// [`KeepDiverse`](crate::KeepDiverse) with a [`Shrinker`](crate::Shrinker),
+    /// earlier picks are never swapped for more different ones.
+    ///
+    /// # Panics
+    ///
+    /// If items have already been pulled, since the items yielded so far
+    /// have no keys to compare against.
+    pub fn diverse_by_key<K2, F2>(self, key: F2) -> ShrinkByClass<I, K2, F2>
+    where
+        K2: Distance,
+        F2: FnMut(&I::Item) -> K2,
+    {
+        assert_eq!(
+            self.seen, 0,
+            "diverse_by_key must be called before iterating"
+        );
+
+        ShrinkByClass {
+            iter: self.iter,
+            classes: ClassMap::new(),
+            per_class: self.per_class,
+            key: Some(key),
+            distinct: |kept, key| kept.iter().all(|kept| kept.distance(key) > 0.0),
+            seen: self.seen,
+            dropped: self.dropped,
+        }
+    }
+
+    /// The number of items pulled from the underlying iterator so far
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// The number of items skipped so far, because enough items of their
+    /// classification had been yielded or their key was not distinct
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+impl<I: Iterator, K, F: FnMut(&I::Item) -> K> Iterator for ShrinkByClass<I, K, F>
+where
+    I::Item: Classify,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.iter.by_ref() {
+            self.seen += 1;
+
+            let class = item.classify();
+
+            let Some((yielded, keys)) = self.classes.get_mut(&class) else {
+                let key = self.key.as_mut().map(|key| key(&item));
+                self.classes.insert(class, (1, key.into_iter().collect()));
+                return Some(item);
+            };
+
+            // Full classes are skipped without computing a key
+            if *yielded >= self.per_class {
+                self.dropped += 1;
+                continue;
+            }
+            if let Some(key) = self.key.as_mut().map(|key| key(&item)) {
+                if !(self.distinct)(keys, &key) {
+                    self.dropped += 1;
+                    continue;
+                }
+                keys.push(key);
+            }
+
+            *yielded += 1;
+            return Some(item);
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I: FusedIterator, K, F: FnMut(&I::Item) -> K> FusedIterator for ShrinkByClass<I, K, F> where
+    I::Item: Classify
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink_by_class() {
+        let items = vec![1u8, 2, 0, 3, 255, 0, 254];
+
+        let mut iter = items.into_iter().shrink_by_class();
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![1, 0, 255]);
+        assert_eq!(iter.seen(), 7);
+        assert_eq!(iter.dropped(), 4);
+    }
+
+    #[test]
+    fn test_shrink_by_class_is_lazy() {
+        let mut pulled = 0;
+        let mut iter = (0u64..).inspect(|_| pulled += 1).shrink_by_class();
+
+        // An infinite input still yields its first classes on demand
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.seen(), 2);
+        assert_eq!(iter.dropped(), 0);
+
+        drop(iter);
+        assert_eq!(pulled, 2);
+    }
+
+    #[test]
+    fn test_shrink_by_class_per_class() {
+        let items = vec![1u8, 2, 0, 3, 255, 0, 254];
+
+        let mut iter = items.into_iter().shrink_by_class().per_class(2);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![1, 2, 0, 255, 0]);
+        assert_eq!(iter.dropped(), 2);
+    }
+
+    #[test]
+    fn test_shrink_by_class_diverse() {
+        let items = vec![10u32, 10, 11, 10, 12, 0];
+
+        let mut iter = items
+            .into_iter()
+            .shrink_by_class()
+            .per_class(3)
+            .diverse_by_key(|x: &u32| *x);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![10, 11, 12, 0]);
+        assert_eq!(iter.seen(), 6);
+        assert_eq!(iter.dropped(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "before iterating")]
+    fn test_shrink_by_class_diverse_after_iterating() {
+        let mut iter = [1u8, 2].into_iter().shrink_by_class();
+        iter.next();
+        let _ = iter.diverse_by_key(|x: &u8| *x);
+    }
+}
@@ -0,0 +1,509 @@
+use crate::class_map::ClassMap;
+use crate::strategy::{self, KeepFirst, Strategy};
+use crate::{Classify, ClassifyContext};
+
+/// Keeps representative items for every classification it sees.
+///
+/// Items are offered one at a time with [`Shrinker::push`]. The first item
+/// seen for a classification is kept, and by default every later item with
+/// the same classification is dropped. A [`Strategy`] given with
+/// [`Shrinker::with_strategy`] can pick a different representative, such
+/// as the smallest, and [`Shrinker::per_class`] keeps more than one.
+///
+/// Only the representatives are held, so memory is bounded by the number
+/// of classifications times the number kept per classification. Along with
+/// them, the shrinker counts how often each classification occurred, see
+/// [`Shrinker::classes`].
+pub struct Shrinker<T: Classify, S = KeepFirst> {
+    classes: ClassMap<T::Output, Class<T>>,
+    strategy: S,
+    per_class: usize,
+    /// Whether two items are the same, so only one of them is kept
+    same: fn(&T, &T) -> bool,
+    total: usize,
+    ctx: ClassifyContext,
+}
+
+/// The representatives of a single classification and how often it
+/// occurred
+struct Class<T> {
+    kept: Vec<T>,
+    count: usize,
+    first_index: usize,
+    last_index: usize,
+}
+
+/// How often a classification occurred among the items offered to a
+/// [`Shrinker`]. Indices count items in the order they were offered,
+/// starting at zero.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ClassStats {
+    /// The number of items with this classification
+    pub count: usize,
+    /// The index of the first item with this classification
+    pub first_index: usize,
+    /// The index of the last item with this classification
+    pub last_index: usize,
+    /// The fraction of all items with this classification
+    pub share: f64,
+}
+
+/// The representatives of a classification along with its [`ClassStats`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShrunkClass<T> {
+    pub representatives: Vec<T>,
+    pub stats: ClassStats,
+}
+
+/// Sorts classes from the rarest to the most common. Classes that are as
+/// common as each other keep the order they were first seen in.
+pub fn sort_by_rarity<T>(classes: &mut [ShrunkClass<T>]) {
+    classes.sort_by_key(|class| (class.stats.count, class.stats.first_index));
+}
+
+impl<T: Classify> Shrinker<T> {
+    pub fn new() -> Self {
+        Self::with_strategy(KeepFirst)
+    }
+}
+
+impl<T: Classify, S: Strategy<T>> Shrinker<T, S> {
+    pub fn with_strategy(strategy: S) -> Self {
+        Self {
+            classes: ClassMap::new(),
+            strategy,
+            per_class: 1,
+            same: |_, _| false,
+            total: 0,
+            ctx: ClassifyContext::DEFAULT,
+        }
+    }
+
+    /// Keeps up to `k` distinct representatives for every classification.
+    /// Items equal to one already kept for their classification are
+    /// counted but never kept.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is zero.
+    pub fn per_class(mut self, k: usize) -> Self
+    where
+        T: PartialEq,
+    {
+        assert!(k > 0, "a shrinker must keep at least one item per class");
+        self.per_class = k;
+        self.same = T::eq;
+        self
+    }
+
+    /// Classifies items with [`Classify::classify_with`] and `ctx` in
+    /// place of the default thresholds.
+    pub fn context(mut self, ctx: ClassifyContext) -> Self {
+        self.ctx = ctx;
+        self
+    }
+
+    /// Offers an item to the shrinker, returning `true` if it was kept as a
+    /// representative of its classification.
+    pub fn push(&mut self, item: T) -> bool {
+        let index = self.total;
+        self.total += 1;
+
+        let key = item.classify_with(&self.ctx);
+        let Some(class) = self.classes.get_mut(&key) else {
+            self.classes.insert(
+                key,
+                Class {
+                    kept: vec![item],
+                    count: 1,
+                    first_index: index,
+                    last_index: index,
+                },
+            );
+            return true;
+        };
+
+        class.count += 1;
+        class.last_index = index;
+
+        if class.kept.iter().any(|kept| (self.same)(kept, &item)) {
+            return false;
+        }
+
+        strategy::offer(
+            &mut self.strategy,
+            &mut class.kept,
+            item,
+            class.count,
+            self.per_class,
+        )
+    }
+
+    /// Combines `other` into this shrinker, as if every item offered to
+    /// `other` had been offered to this shrinker after its own items.
+    ///
+    /// The representatives of each classification are combined with
+    /// [`Strategy::merge`], and the stats are summed, with the indices of
+    /// `other` moved past the items of this shrinker. Merging is
+    /// associative, so shards of the input can be shrunk separately and
+    /// merged in order. This shrinker's strategy is kept. Classes only
+    /// `other` has are passed through it too, so they keep at most
+    /// `per_class` items like the others.
+    ///
+    /// # Panics
+    ///
+    /// If `other` keeps a different number of items per class, or
+    /// classifies with a different context.
+    pub fn merge(&mut self, other: Self) {
+        assert_eq!(
+            self.per_class, other.per_class,
+            "can only merge shrinkers that keep as many items per class"
+        );
+        assert!(
+            self.ctx == other.ctx,
+            "can only merge shrinkers that classify with the same context"
+        );
+
+        let offset = self.total;
+        self.total += other.total;
+
+        for (key, other) in other.classes.into_iter() {
+            let Some(class) = self.classes.get_mut(&key) else {
+                let mut kept = Vec::new();
+                self.strategy
+                    .merge(&mut kept, 0, other.kept, other.count, self.per_class);
+                self.classes.insert(
+                    key,
+                    Class {
+                        kept,
+                        count: other.count,
+                        first_index: other.first_index + offset,
+                        last_index: other.last_index + offset,
+                    },
+                );
+                continue;
+            };
+
+            let same = self.same;
+            let mut other_kept = other.kept;
+            other_kept.retain(|item| !class.kept.iter().any(|kept| same(kept, item)));
+
+            self.strategy.merge(
+                &mut class.kept,
+                class.count,
+                other_kept,
+                other.count,
+                self.per_class,
+            );
+            class.count += other.count;
+            class.last_index = other.last_index + offset;
+        }
+    }
+
+    /// The number of distinct classifications seen so far
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of items offered so far
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The representatives kept so far, grouped by classification in the
+    /// order the classifications were first seen
+    pub fn representatives(&self) -> impl Iterator<Item = &T> {
+        self.classes.iter().flat_map(|(_, class)| &class.kept)
+    }
+
+    /// The representatives and stats of every classification seen so far,
+    /// in the order the classifications were first seen
+    pub fn classes(&self) -> impl Iterator<Item = (&[T], ClassStats)> {
+        self.classes
+            .iter()
+            .map(|(_, class)| (class.kept.as_slice(), self.stats(class)))
+    }
+
+    /// Consumes the shrinker, returning the representatives grouped by
+    /// classification in the order the classifications were first seen
+    pub fn into_representatives(self) -> Vec<T> {
+        self.classes
+            .into_iter()
+            .flat_map(|(_, class)| class.kept)
+            .collect()
+    }
+
+    /// Consumes the shrinker, returning the representatives and stats of
+    /// every classification in the order the classifications were first
+    /// seen. See [`sort_by_rarity`] to put the rarest classes first.
+    pub fn into_classes(self) -> Vec<ShrunkClass<T>> {
+        let total = self.total;
+        self.classes
+            .into_iter()
+            .map(|(_, class)| ShrunkClass {
+                stats: class_stats(&class, total),
+                representatives: class.kept,
+            })
+            .collect()
+    }
+
+    fn stats(&self, class: &Class<T>) -> ClassStats {
+        class_stats(class, self.total)
+    }
+}
+
+impl<T: Classify, S: Strategy<T> + Default> Default for Shrinker<T, S> {
+    fn default() -> Self {
+        Self::with_strategy(S::default())
+    }
+}
+
+impl<T: Classify, S: Strategy<T>> Extend<T> for Shrinker<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: Classify, S: Strategy<T> + Default> FromIterator<T> for Shrinker<T, S> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut shrinker = Self::default();
+        shrinker.extend(iter);
+        shrinker
+    }
+}
+
+/// Reduces a list of items to one representative per classification.
+///
+/// The first item seen for each classification is kept, and the
+/// representatives are returned in the order they were first seen.
+pub fn shrink<T: Classify, I: IntoIterator<Item = T>>(items: I) -> Vec<T> {
+    items
+        .into_iter()
+        .collect::<Shrinker<T>>()
+        .into_representatives()
+}
+
+fn class_stats<T>(class: &Class<T>, total: usize) -> ClassStats {
+    ClassStats {
+        count: class.count,
+        first_index: class.first_index,
+        last_index: class.last_index,
+        share: class.count as f64 / total as f64,
+    }
+}
+
+/// Reduces a list of items to one representative per classification,
+/// along with how often each classification occurred.
+///
+/// The classes are returned in the order they were first seen.
+pub fn shrink_with_stats<T: Classify, I: IntoIterator<Item = T>>(items: I) -> Vec<ShrunkClass<T>> {
+    items.into_iter().collect::<Shrinker<T>>().into_classes()
+}
+
+/// Reduces a list of items to one representative per classification,
+/// chosen by `strategy`.
+///
+/// The representatives are returned in the order their classifications
+/// were first seen.
+pub fn shrink_with<T, S, I>(items: I, strategy: S) -> Vec<T>
+where
+    T: Classify,
+    S: Strategy<T>,
+    I: IntoIterator<Item = T>,
+{
+    let mut shrinker = Shrinker::with_strategy(strategy);
+    shrinker.extend(items);
+    shrinker.into_representatives()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CivilDate;
+
+    #[test]
+    fn test_shrink_keeps_first_of_each_class() {
+        let items = vec![1u8, 2, 0, 3, 255, 0, 254];
+
+        assert_eq!(shrink(items), vec![1, 0, 255]);
+    }
+
+    #[test]
+    fn test_shrink_empty() {
+        let items: Vec<String> = vec![];
+
+        assert!(shrink(items).is_empty());
+    }
+
+    #[test]
+    fn test_shrinker_push() {
+        let mut shrinker = Shrinker::new();
+
+        assert!(shrinker.is_empty());
+        assert!(shrinker.push("hello"));
+        assert!(!shrinker.push("world"));
+        assert!(shrinker.push(""));
+        assert!(shrinker.push(" "));
+        assert!(!shrinker.push("\t"));
+
+        assert_eq!(shrinker.len(), 3);
+        assert_eq!(
+            shrinker.representatives().copied().collect::<Vec<_>>(),
+            vec!["hello", "", " "]
+        );
+        assert_eq!(shrinker.into_representatives(), vec!["hello", "", " "]);
+    }
+
+    #[test]
+    fn test_shrinker_with_strategy() {
+        let mut shrinker = Shrinker::with_strategy(crate::KeepLast);
+
+        assert!(shrinker.push(1u8));
+        assert!(shrinker.push(2));
+        assert!(shrinker.push(0));
+
+        let mut shrinker = Shrinker::with_strategy(crate::KeepSmallest::by_key(|x: &u8| *x));
+
+        assert!(shrinker.push(5u8));
+        assert!(!shrinker.push(7));
+        assert!(shrinker.push(2));
+        assert_eq!(shrinker.into_representatives(), vec![2]);
+    }
+
+    #[test]
+    fn test_shrinker_per_class() {
+        let mut shrinker = Shrinker::new().per_class(2);
+
+        assert!(shrinker.push(1u8));
+        assert!(shrinker.push(0));
+        assert!(shrinker.push(2));
+        assert!(!shrinker.push(3));
+
+        assert_eq!(shrinker.len(), 2);
+        assert_eq!(shrinker.into_representatives(), vec![1, 2, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shrinker_per_class_zero() {
+        let _ = Shrinker::<u8>::new().per_class(0);
+    }
+
+    #[test]
+    fn test_class_stats() {
+        let mut classes = shrink_with_stats(vec![1u8, 2, 0, 3, 255, 4, 5, 0]);
+
+        assert_eq!(
+            classes[0],
+            ShrunkClass {
+                representatives: vec![1],
+                stats: ClassStats {
+                    count: 5,
+                    first_index: 0,
+                    last_index: 6,
+                    share: 5.0 / 8.0,
+                },
+            }
+        );
+
+        sort_by_rarity(&mut classes);
+        let rarest: Vec<(u8, usize)> = classes
+            .iter()
+            .map(|class| (class.representatives[0], class.stats.count))
+            .collect();
+        assert_eq!(rarest, vec![(255, 1), (0, 2), (1, 5)]);
+    }
+
+    #[test]
+    fn test_shrinker_classes() {
+        let mut shrinker = Shrinker::new().per_class(2);
+        shrinker.extend(["a", "", "b", "c"]);
+
+        assert_eq!(shrinker.total(), 4);
+
+        let classes: Vec<_> = shrinker.classes().collect();
+        assert_eq!(classes[0].0, &["a", "b"]);
+        assert_eq!(classes[0].1.count, 3);
+        assert_eq!(classes[0].1.last_index, 3);
+        assert_eq!(classes[1].0, &[""]);
+        assert_eq!(classes[1].1.share, 0.25);
+    }
+
+    #[test]
+    fn test_shrinker_context() {
+        let ctx = ClassifyContext {
+            len_buckets: vec![0, 1, 10].into(),
+            ..ClassifyContext::DEFAULT
+        };
+        let items = vec![vec![1u8; 2], vec![1; 5], vec![1; 20]];
+
+        assert_eq!(shrink(items.clone()).len(), 1);
+
+        let mut shrinker = Shrinker::new().context(ctx);
+        shrinker.extend(items);
+        assert_eq!(shrinker.len(), 2);
+    }
+
+    #[test]
+    fn test_shrinker_merge() {
+        let items = vec![1u8, 2, 0, 3, 255, 4, 5, 0];
+
+        let mut left: Shrinker<u8> = items[..3].iter().copied().collect();
+        let right: Shrinker<u8> = items[3..].iter().copied().collect();
+        left.merge(right);
+
+        assert_eq!(left.total(), 8);
+        assert_eq!(left.into_classes(), shrink_with_stats(items));
+    }
+
+    #[test]
+    #[should_panic(expected = "as many items per class")]
+    fn test_shrinker_merge_checks_per_class() {
+        let mut left = Shrinker::<u8>::new().per_class(1);
+        left.merge(Shrinker::new().per_class(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "the same context")]
+    fn test_shrinker_merge_checks_context() {
+        let ctx = ClassifyContext {
+            epoch: CivilDate::new(1900, 1, 1),
+            ..ClassifyContext::DEFAULT
+        };
+        let mut left = Shrinker::<u8>::new();
+        left.merge(Shrinker::new().context(ctx));
+    }
+
+    #[test]
+    fn test_shrinker_merge_is_associative() {
+        let shard = |items: &[u8]| items.iter().copied().collect::<Shrinker<u8>>();
+        let items = [0u8, 7, 255, 0, 3, 255, 9, 0];
+
+        let mut left = shard(&items[..2]);
+        left.merge(shard(&items[2..5]));
+        left.merge(shard(&items[5..]));
+
+        let mut right = shard(&items[2..5]);
+        right.merge(shard(&items[5..]));
+        let mut nested = shard(&items[..2]);
+        nested.merge(right);
+
+        assert_eq!(left.into_classes(), nested.into_classes());
+    }
+
+    #[test]
+    fn test_shrinker_with_float_output() {
+        // Every NaN shares a single classification
+        let shrinker = [1.0f64, 2.0, f64::NAN, -1.0, f64::NAN, 0.0]
+            .into_iter()
+            .collect::<Shrinker<_>>();
+
+        assert_eq!(shrinker.len(), 4);
+    }
+}
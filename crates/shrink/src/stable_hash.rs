@@ -0,0 +1,387 @@
+use crate::{
+    CalendarClassification, CalendarDay, CalendarWeek, CalendarZone, DurationClassification,
+    FloatClassification, IntClassification, NaiveDateClassification, NaiveDateTimeClassification,
+    StringClassification, SystemTimeClassification,
+};
+use std::hash::Hasher;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A [`Hasher`] whose output does not depend on the platform or its
+/// endianness.
+///
+/// Composite classifications (tuples, slices and derived types) are
+/// collapsed into a `u64` with this hasher, so their keys can be stored
+/// and compared across builds. The scheme is 64 bit FNV-1a over a byte
+/// stream where
+///
+/// - integers are written as little endian bytes
+/// - `usize` and `isize` are widened to 64 bits before being written
+/// - byte slices are written as-is
+///
+/// Classifications are reduced to those writes by their [`StableHash`]
+/// impl rather than by [`Hash`](std::hash::Hash), so the keys do not change
+/// between Rust releases, platforms or endianness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    pub const fn new() -> Self {
+        Self {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The integer encoding shared by [`StableHasher`] and [`StableBytes`],
+/// defined in terms of `write`
+macro_rules! stable_integer_writes {
+    () => {
+        fn write_u8(&mut self, i: u8) {
+            self.write(&[i]);
+        }
+
+        fn write_u16(&mut self, i: u16) {
+            self.write(&i.to_le_bytes());
+        }
+
+        fn write_u32(&mut self, i: u32) {
+            self.write(&i.to_le_bytes());
+        }
+
+        fn write_u64(&mut self, i: u64) {
+            self.write(&i.to_le_bytes());
+        }
+
+        fn write_u128(&mut self, i: u128) {
+            self.write(&i.to_le_bytes());
+        }
+
+        fn write_usize(&mut self, i: usize) {
+            self.write_u64(i as u64);
+        }
+
+        fn write_i8(&mut self, i: i8) {
+            self.write_u8(i as u8);
+        }
+
+        fn write_i16(&mut self, i: i16) {
+            self.write(&i.to_le_bytes());
+        }
+
+        fn write_i32(&mut self, i: i32) {
+            self.write(&i.to_le_bytes());
+        }
+
+        fn write_i64(&mut self, i: i64) {
+            self.write(&i.to_le_bytes());
+        }
+
+        fn write_i128(&mut self, i: i128) {
+            self.write(&i.to_le_bytes());
+        }
+
+        fn write_isize(&mut self, i: isize) {
+            self.write_i64(i as i64);
+        }
+    };
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    stable_integer_writes!();
+}
+
+/// Records the bytes a value feeds a [`StableHasher`], so they can be
+/// replayed into several hashers later. Finishes with the hash of the
+/// recorded bytes.
+#[derive(Debug, Default)]
+pub(crate) struct StableBytes(pub(crate) Vec<u8>);
+
+impl StableBytes {
+    pub(crate) fn of<T: StableHash + ?Sized>(value: &T) -> Self {
+        let mut bytes = Self::default();
+        value.stable_hash(&mut bytes);
+        bytes
+    }
+}
+
+impl Hasher for StableBytes {
+    fn finish(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write(&self.0);
+        hasher.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    stable_integer_writes!();
+}
+
+/// Hashes a single value with a fresh [`StableHasher`]
+pub fn stable_hash<T: StableHash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = StableHasher::new();
+    value.stable_hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Feeds a value to a hasher with an encoding this crate defines, in place
+/// of [`Hash`](std::hash::Hash), whose encoding std is free to change.
+///
+/// - integers and `bool` are written with the matching `write_*` call,
+///   `bool` as a `u8`
+/// - strings are written as their length, as a `u64`, then their bytes
+/// - `Option` writes a `u8` tag, `0` for `None` and `1` for `Some`, then
+///   the value
+/// - the classification enums write their variant's index as a `u8`, and
+///   [`CalendarClassification`] writes its fields in order
+///
+/// `#[derive(Classify)]` implements it for the classifications it
+/// generates. Every [`Classify::Output`](crate::Classify::Output) must
+/// implement it.
+pub trait StableHash {
+    fn stable_hash<H: Hasher>(&self, state: &mut H);
+}
+
+impl<T: StableHash + ?Sized> StableHash for &T {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).stable_hash(state);
+    }
+}
+
+macro_rules! stable_hash_integers {
+    ($($ty:ty => $write:ident),* $(,)?) => {
+        $(
+            impl StableHash for $ty {
+                fn stable_hash<H: Hasher>(&self, state: &mut H) {
+                    state.$write(*self);
+                }
+            }
+        )*
+    };
+}
+
+stable_hash_integers!(
+    u8 => write_u8,
+    u16 => write_u16,
+    u32 => write_u32,
+    u64 => write_u64,
+    u128 => write_u128,
+    i8 => write_i8,
+    i16 => write_i16,
+    i32 => write_i32,
+    i64 => write_i64,
+    i128 => write_i128,
+);
+
+// Widened here rather than left to the hasher, which may be any `Hasher`
+impl StableHash for usize {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(*self as u64);
+    }
+}
+
+impl StableHash for isize {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_i64(*self as i64);
+    }
+}
+
+impl StableHash for bool {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u8(u8::from(*self));
+    }
+}
+
+impl StableHash for str {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.len() as u64);
+        state.write(self.as_bytes());
+    }
+}
+
+impl StableHash for String {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().stable_hash(state);
+    }
+}
+
+impl<T: StableHash> StableHash for Option<T> {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            None => state.write_u8(0),
+            Some(value) => {
+                state.write_u8(1);
+                value.stable_hash(state);
+            }
+        }
+    }
+}
+
+/// Implements [`StableHash`] for a fieldless enum by writing its variant's
+/// index, so reordering the variants is a breaking change
+macro_rules! stable_hash_variant_index {
+    ($($(#[$attr:meta])* $ty:ty),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            impl StableHash for $ty {
+                fn stable_hash<H: Hasher>(&self, state: &mut H) {
+                    state.write_u8(*self as u8);
+                }
+            }
+        )*
+    };
+}
+
+stable_hash_variant_index!(
+    IntClassification,
+    FloatClassification,
+    StringClassification,
+    DurationClassification,
+    SystemTimeClassification,
+    NaiveDateClassification,
+    NaiveDateTimeClassification,
+    #[cfg(feature = "chrono")]
+    crate::NaiveTimeClassification,
+    CalendarDay,
+    CalendarWeek,
+    CalendarZone,
+);
+
+impl StableHash for CalendarClassification {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        self.day.stable_hash(state);
+        self.week.stable_hash(state);
+        self.zone.stable_hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Classify;
+
+    #[test]
+    fn test_fnv1a_reference_values() {
+        // Reference values for 64 bit FNV-1a
+        let mut hasher = StableHasher::new();
+        hasher.write(b"");
+        assert_eq!(hasher.finish(), 0xcbf29ce484222325);
+
+        let mut hasher = StableHasher::new();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63dc4c8601ec8c);
+
+        let mut hasher = StableHasher::new();
+        hasher.write(b"foobar");
+        assert_eq!(hasher.finish(), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn test_integers_are_little_endian() {
+        let mut a = StableHasher::new();
+        a.write_u32(0x0102_0304);
+        let mut b = StableHasher::new();
+        b.write(&[4, 3, 2, 1]);
+        assert_eq!(a.finish(), b.finish());
+
+        // Pointer sized integers are always widened to 64 bits
+        let mut a = StableHasher::new();
+        a.write_usize(7);
+        let mut b = StableHasher::new();
+        b.write_u64(7);
+        assert_eq!(a.finish(), b.finish());
+
+        let mut a = StableHasher::new();
+        a.write_isize(-7);
+        let mut b = StableHasher::new();
+        b.write_i64(-7);
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    /// The hash of `bytes` written in one go
+    fn fnv1a(bytes: &[u8]) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(stable_hash(&true), fnv1a(&[1]));
+        assert_eq!(stable_hash("a"), fnv1a(&[1, 0, 0, 0, 0, 0, 0, 0, b'a']));
+        assert_eq!(stable_hash(&7usize), fnv1a(&[7, 0, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(stable_hash(&IntClassification::Max), fnv1a(&[3]));
+        assert_eq!(stable_hash(&None::<IntClassification>), fnv1a(&[0]));
+        assert_eq!(stable_hash(&Some(IntClassification::Max)), fnv1a(&[1, 3]));
+        assert_eq!(
+            stable_hash(&CalendarClassification {
+                day: CalendarDay::LeapDay,
+                week: CalendarWeek::Weekend,
+                zone: CalendarZone::Utc,
+            }),
+            fnv1a(&[2, 1, 1])
+        );
+
+        // Strings are length prefixed, so moving bytes between adjacent
+        // strings changes the hash
+        let pair = |a: &str, b: &str| {
+            let mut hasher = StableHasher::new();
+            a.stable_hash(&mut hasher);
+            b.stable_hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_ne!(pair("ab", ""), pair("a", "b"));
+    }
+
+    #[test]
+    fn test_golden_values() {
+        assert_eq!(stable_hash(&0u8), 0xaf63bd4c8601b7df);
+        assert_eq!(stable_hash(&1u64), 0x89cd31291d2aefa4);
+        assert_eq!(stable_hash(&true), 0xaf63bc4c8601b62c);
+        assert_eq!(stable_hash("a"), 0x529a4ddc8ff56bbf);
+        assert_eq!(stable_hash(&IntClassification::Zero), 0xaf63bd4c8601b7df);
+        assert_eq!(stable_hash(&IntClassification::Min), 0xaf63b94c8601b113);
+        assert_eq!(stable_hash(&FloatClassification::NaN), 0xaf63b84c8601af60);
+        assert_eq!(
+            stable_hash(&StringClassification::NonEmpty),
+            0xaf63bf4c8601bb45
+        );
+        assert_eq!(
+            stable_hash(&Some(IntClassification::Positive)),
+            0x082f2307b4e88e77
+        );
+    }
+
+    #[test]
+    fn test_golden_classification_keys() {
+        // These keys may be stored outside the process, so a change here
+        // is a breaking change
+        assert_eq!((1u8, "a").classify(), 0x082f2407b4e8902a);
+        assert_eq!((1u8, "a", 1.5f32, true).classify(), 0xbe812577516b3630);
+        assert_eq!(Vec::<u8>::new().classify(), 0x4d25767f9dce13f5);
+        assert_eq!(vec![0u8, 1, 2].classify(), 0x9111c4600580fbfc);
+        assert_eq!(vec!["", "a"].classify(), 0x9111c76005810115);
+    }
+}
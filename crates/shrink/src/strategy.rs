@@ -0,0 +1,479 @@
+/// Chooses the representatives a [`Shrinker`](crate::Shrinker) keeps for
+/// each classification.
+///
+/// The shrinker keeps up to `k` representatives per classification, one by
+/// default. Items are offered to the strategy with [`Strategy::admit`]
+/// while there is room left, and with [`Strategy::replace`] once the
+/// classification is full.
+///
+/// Shrinkers of the same item type can be combined with
+/// [`Shrinker::merge`](crate::Shrinker::merge), which asks the strategy to
+/// [`Strategy::merge`] the representatives of each classification.
+pub trait Strategy<T> {
+    /// Whether `candidate` should be kept while the classification has room
+    /// for more representatives. Keeps every item by default.
+    fn admit(&mut self, kept: &[T], candidate: &T) -> bool {
+        let _ = (kept, candidate);
+        true
+    }
+
+    /// Which of the `kept` representatives, if any, `candidate` should
+    /// replace. `kept` is in the order the items were kept, and `seen` is
+    /// the number of items of this classification so far, including
+    /// `candidate`.
+    fn replace(&mut self, kept: &[T], candidate: &T, seen: usize) -> Option<usize>;
+
+    /// Combines the representatives of two runs over the same
+    /// classification, where `other` came from the `other_seen` items after
+    /// the `seen` items `kept` came from. At most `per_class` items are left
+    /// in `kept`.
+    ///
+    /// By default each of `other` is offered in turn as if it had been
+    /// pushed after `kept`, which is what the order and key based
+    /// strategies need.
+    fn merge(
+        &mut self,
+        kept: &mut Vec<T>,
+        seen: usize,
+        other: Vec<T>,
+        other_seen: usize,
+        per_class: usize,
+    ) {
+        let skipped = other_seen - other.len();
+        for (i, item) in other.into_iter().enumerate() {
+            offer(self, kept, item, seen + skipped + i + 1, per_class);
+        }
+    }
+}
+
+/// Offers `candidate` to `strategy` as the `seen`-th item of its
+/// classification, returning `true` if it was kept
+pub(crate) fn offer<T, S: Strategy<T> + ?Sized>(
+    strategy: &mut S,
+    kept: &mut Vec<T>,
+    candidate: T,
+    seen: usize,
+    per_class: usize,
+) -> bool {
+    if kept.len() < per_class {
+        if !strategy.admit(kept, &candidate) {
+            return false;
+        }
+    } else {
+        let Some(replaced) = strategy.replace(kept, &candidate, seen) else {
+            return false;
+        };
+        kept.remove(replaced);
+    }
+
+    kept.push(candidate);
+    true
+}
+
+/// Keeps the first items of each classification
+#[derive(Debug, Default, Copy, Clone)]
+pub struct KeepFirst;
+
+impl<T> Strategy<T> for KeepFirst {
+    fn replace(&mut self, _kept: &[T], _candidate: &T, _seen: usize) -> Option<usize> {
+        None
+    }
+}
+
+/// Keeps the last items of each classification
+#[derive(Debug, Default, Copy, Clone)]
+pub struct KeepLast;
+
+impl<T> Strategy<T> for KeepLast {
+    fn replace(&mut self, _kept: &[T], _candidate: &T, _seen: usize) -> Option<usize> {
+        Some(0)
+    }
+}
+
+/// Keeps the items with the smallest keys, such as their size, in each
+/// classification. Ties keep the earlier item.
+#[derive(Debug, Copy, Clone)]
+pub struct KeepSmallest<F> {
+    key: F,
+}
+
+impl<F> KeepSmallest<F> {
+    pub fn by_key(key: F) -> Self {
+        Self { key }
+    }
+}
+
+impl<T, K: Ord, F: FnMut(&T) -> K> Strategy<T> for KeepSmallest<F> {
+    fn replace(&mut self, kept: &[T], candidate: &T, _seen: usize) -> Option<usize> {
+        let candidate = (self.key)(candidate);
+        let (largest, key) = kept
+            .iter()
+            .map(&mut self.key)
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.cmp(b))?;
+
+        (candidate < key).then_some(largest)
+    }
+}
+
+/// Keeps the items with the largest keys in each classification. Ties keep
+/// the earlier item.
+#[derive(Debug, Copy, Clone)]
+pub struct KeepLargest<F> {
+    key: F,
+}
+
+impl<F> KeepLargest<F> {
+    pub fn by_key(key: F) -> Self {
+        Self { key }
+    }
+}
+
+impl<T, K: Ord, F: FnMut(&T) -> K> Strategy<T> for KeepLargest<F> {
+    fn replace(&mut self, kept: &[T], candidate: &T, _seen: usize) -> Option<usize> {
+        let candidate = (self.key)(candidate);
+        let (smallest, key) = kept
+            .iter()
+            .map(&mut self.key)
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.cmp(b))?;
+
+        (candidate > key).then_some(smallest)
+    }
+}
+
+/// Keeps uniformly random items of each classification, using reservoir
+/// sampling so the items do not need to be stored.
+///
+/// The same seed and the same input always pick the same items. When
+/// shards are shrunk separately and merged, give each shard its own seed.
+#[derive(Debug, Copy, Clone)]
+pub struct KeepRandom {
+    state: u64,
+}
+
+impl KeepRandom {
+    pub fn with_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl<T> Strategy<T> for KeepRandom {
+    fn replace(&mut self, kept: &[T], _candidate: &T, seen: usize) -> Option<usize> {
+        // The n-th item is kept with probability k/n, in place of a random
+        // representative
+        let slot = (self.next_u64() % seen as u64) as usize;
+        (slot < kept.len()).then_some(slot)
+    }
+
+    fn merge(
+        &mut self,
+        kept: &mut Vec<T>,
+        seen: usize,
+        other: Vec<T>,
+        other_seen: usize,
+        per_class: usize,
+    ) {
+        // Both sides are uniform samples of their items, so drawing from
+        // each in proportion to the items left on that side gives a uniform
+        // sample of all of them
+        let mut left = std::mem::take(kept).into_iter();
+        let mut right = other.into_iter();
+        let (mut left_seen, mut right_seen) = (seen, other_seen);
+
+        while kept.len() < per_class && left_seen + right_seen > 0 {
+            let pick = (self.next_u64() % (left_seen + right_seen) as u64) as usize;
+            if pick < left_seen {
+                left_seen -= 1;
+                kept.extend(left.next());
+            } else {
+                right_seen -= 1;
+                kept.extend(right.next());
+            }
+        }
+    }
+}
+
+/// Keeps items that are as different from each other as possible, as
+/// measured by the [`Distance`] between their keys.
+///
+/// The first item of each classification is always kept. While there is
+/// room, items are only kept if their key differs from every kept key.
+/// Once full, an item replaces a later pick if that increases the smallest
+/// distance between any two kept keys.
+#[derive(Debug, Copy, Clone)]
+pub struct KeepDiverse<F> {
+    key: F,
+}
+
+impl<F> KeepDiverse<F> {
+    pub fn by_key(key: F) -> Self {
+        Self { key }
+    }
+}
+
+impl<T, K: Distance, F: FnMut(&T) -> K> Strategy<T> for KeepDiverse<F> {
+    fn admit(&mut self, kept: &[T], candidate: &T) -> bool {
+        let candidate = (self.key)(candidate);
+        kept.iter()
+            .all(|kept| (self.key)(kept).distance(&candidate) > 0.0)
+    }
+
+    fn replace(&mut self, kept: &[T], candidate: &T, _seen: usize) -> Option<usize> {
+        let candidate = (self.key)(candidate);
+        let keys: Vec<K> = kept.iter().map(&mut self.key).collect();
+
+        // The smallest distance between the kept keys, with `replaced`
+        // swapped for the candidate
+        let spread = |replaced: Option<usize>| {
+            let key = |i: usize| match replaced {
+                Some(r) if r == i => &candidate,
+                _ => &keys[i],
+            };
+
+            let mut spread = f64::INFINITY;
+            for i in 0..keys.len() {
+                for j in i + 1..keys.len() {
+                    spread = spread.min(key(i).distance(key(j)));
+                }
+            }
+            spread
+        };
+
+        let current = spread(None);
+        let (best, best_spread) = (1..keys.len())
+            .map(|i| (i, spread(Some(i))))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+        (best_spread > current).then_some(best)
+    }
+}
+
+/// How far apart two keys are, for [`KeepDiverse`]
+pub trait Distance {
+    fn distance(&self, other: &Self) -> f64;
+}
+
+macro_rules! impl_distance_for_number {
+    ($($t:ty),+) => {
+        $(
+            impl Distance for $t {
+                fn distance(&self, other: &Self) -> f64 {
+                    (*self as f64 - *other as f64).abs()
+                }
+            }
+        )+
+    }
+}
+
+impl_distance_for_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+/// Euclidean distance
+impl<const N: usize> Distance for [f64; N] {
+    fn distance(&self, other: &Self) -> f64 {
+        self.iter()
+            .zip(other)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+/// Decides with a closure called as `f(kept, candidate)`, returning the
+/// index of the representative to replace, if any
+#[derive(Debug, Copy, Clone)]
+pub struct KeepCustom<F>(pub F);
+
+impl<T, F: FnMut(&[T], &T) -> Option<usize>> Strategy<T> for KeepCustom<F> {
+    fn replace(&mut self, kept: &[T], candidate: &T, _seen: usize) -> Option<usize> {
+        (self.0)(kept, candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shrink_with, Shrinker};
+
+    fn shrink_k<T: crate::Classify + PartialEq, S: Strategy<T>>(
+        items: Vec<T>,
+        k: usize,
+        strategy: S,
+    ) -> Vec<T> {
+        let mut shrinker = Shrinker::with_strategy(strategy).per_class(k);
+        shrinker.extend(items);
+        shrinker.into_representatives()
+    }
+
+    #[test]
+    fn test_first_and_last() {
+        let items = vec![1u8, 2, 0, 3, 0];
+
+        assert_eq!(shrink_with(items.clone(), KeepFirst), vec![1, 0]);
+        assert_eq!(shrink_with(items.clone(), KeepLast), vec![3, 0]);
+
+        // The second 0 is the same as the first, so it is not kept again
+        assert_eq!(shrink_k(items.clone(), 2, KeepFirst), vec![1, 2, 0]);
+        assert_eq!(shrink_k(items, 2, KeepLast), vec![2, 3, 0]);
+    }
+
+    #[test]
+    fn test_smallest_and_largest() {
+        let items = vec!["hello", "a", "", "hi", "longest", "b"];
+
+        assert_eq!(
+            shrink_with(items.clone(), KeepSmallest::by_key(|s: &&str| s.len())),
+            vec!["a", ""]
+        );
+        assert_eq!(
+            shrink_with(items.clone(), KeepLargest::by_key(|s: &&str| s.len())),
+            vec!["longest", ""]
+        );
+
+        assert_eq!(
+            shrink_k(items.clone(), 2, KeepSmallest::by_key(|s: &&str| s.len())),
+            vec!["a", "b", ""]
+        );
+        assert_eq!(
+            shrink_k(items, 2, KeepLargest::by_key(|s: &&str| s.len())),
+            vec!["hello", "longest", ""]
+        );
+    }
+
+    #[test]
+    fn test_random_is_deterministic() {
+        let items: Vec<u32> = (1..100).collect();
+
+        let a = shrink_with(items.clone(), KeepRandom::with_seed(7));
+        let b = shrink_with(items.clone(), KeepRandom::with_seed(7));
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 1);
+
+        assert_eq!(shrink_k(items, 5, KeepRandom::with_seed(7)).len(), 5);
+    }
+
+    #[test]
+    fn test_random_is_uniform() {
+        let mut picks = [0usize; 4];
+        for seed in 0..4000 {
+            let kept = shrink_with([1u8, 2, 3, 4], KeepRandom::with_seed(seed));
+            picks[kept[0] as usize - 1] += 1;
+        }
+
+        for count in picks {
+            assert!((850..1150).contains(&count), "{picks:?}");
+        }
+    }
+
+    fn merge_k<T: crate::Classify + PartialEq, S: Strategy<T> + Clone>(
+        items: Vec<T>,
+        split: usize,
+        k: usize,
+        strategy: S,
+    ) -> Vec<T> {
+        let mut items = items;
+        let right = items.split_off(split);
+
+        let mut left_shrinker = Shrinker::with_strategy(strategy.clone()).per_class(k);
+        left_shrinker.extend(items);
+        let mut right_shrinker = Shrinker::with_strategy(strategy).per_class(k);
+        right_shrinker.extend(right);
+
+        left_shrinker.merge(right_shrinker);
+        left_shrinker.into_representatives()
+    }
+
+    #[test]
+    fn test_merge_matches_sequential() {
+        let items = vec![4u32, 9, 0, 2, 7, 1, 8, 3, 0, 6];
+
+        for split in 0..=items.len() {
+            for k in 1..4 {
+                assert_eq!(
+                    merge_k(items.clone(), split, k, KeepFirst),
+                    shrink_k(items.clone(), k, KeepFirst)
+                );
+                assert_eq!(
+                    merge_k(items.clone(), split, k, KeepLast),
+                    shrink_k(items.clone(), k, KeepLast)
+                );
+
+                let smallest = KeepSmallest::by_key(|x: &u32| *x);
+                let mut merged = merge_k(items.clone(), split, k, smallest);
+                let mut sequential = shrink_k(items.clone(), k, smallest);
+                merged.sort();
+                sequential.sort();
+                assert_eq!(merged, sequential);
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_random_is_uniform() {
+        // An uneven split still picks every item equally often, as long as
+        // each shard has its own seed
+        let mut picks = [0usize; 4];
+        for seed in 0..4000 {
+            let mut left = Shrinker::with_strategy(KeepRandom::with_seed(seed));
+            left.push(1u8);
+            let mut right = Shrinker::with_strategy(KeepRandom::with_seed(!seed));
+            right.extend([2, 3, 4]);
+
+            left.merge(right);
+            picks[left.into_representatives()[0] as usize - 1] += 1;
+        }
+
+        for count in picks {
+            assert!((850..1150).contains(&count), "{picks:?}");
+        }
+
+        assert_eq!(
+            merge_k((1..20u8).collect(), 5, 4, KeepRandom::with_seed(3)).len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_diverse() {
+        let items = vec![10u32, 11, 10, 12, 50, 13, 100, 14];
+
+        // Duplicates are not kept while filling up
+        assert_eq!(
+            shrink_k(items[..3].to_vec(), 3, KeepDiverse::by_key(|x: &u32| *x)),
+            vec![10, 11]
+        );
+
+        // Later picks spread out from the first one
+        assert_eq!(
+            shrink_k(items, 3, KeepDiverse::by_key(|x: &u32| *x)),
+            vec![10, 50, 100]
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(3u8.distance(&7), 4.0);
+        assert_eq!((-2i64).distance(&2), 4.0);
+        assert_eq!([0.0, 0.0].distance(&[3.0, 4.0]), 5.0);
+    }
+
+    #[test]
+    fn test_custom() {
+        // Prefer even numbers
+        let strategy = KeepCustom(|kept: &[u32], candidate: &u32| {
+            let odd = kept.iter().position(|x| !x.is_multiple_of(2))?;
+            candidate.is_multiple_of(2).then_some(odd)
+        });
+
+        assert_eq!(shrink_with(vec![1u32, 3, 4, 6, 0], strategy), vec![4, 0]);
+    }
+}
@@ -1,6 +1,6 @@
-use crate::Classify;
+use crate::{Cardinality, ClassKey, ClassSpace, Classify};
 
-#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum StringClassification {
     /// An empty string
     Empty,
@@ -13,6 +13,8 @@ pub enum StringClassification {
 impl Classify for String {
     type Output = StringClassification;
 
+    const MAX_CLASSES: Option<u128> = Some(3);
+
     fn classify(&self) -> Self::Output {
         if self.is_empty() {
             StringClassification::Empty
@@ -22,12 +24,18 @@ impl Classify for String {
             StringClassification::NonEmpty
         }
     }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
 }
 
 // Implement for &str as well
 impl Classify for &str {
     type Output = StringClassification;
 
+    const MAX_CLASSES: Option<u128> = Some(3);
+
     fn classify(&self) -> Self::Output {
         if self.is_empty() {
             StringClassification::Empty
@@ -37,6 +45,44 @@ impl Classify for &str {
             StringClassification::NonEmpty
         }
     }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+}
+
+const STRING_CLASSES: [StringClassification; 3] = [
+    StringClassification::Empty,
+    StringClassification::Whitespace,
+    StringClassification::NonEmpty,
+];
+
+impl ClassSpace for String {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(STRING_CLASSES.len() as u128)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        STRING_CLASSES.into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+impl ClassSpace for &str {
+    fn cardinality() -> Cardinality {
+        String::cardinality()
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        String::classes()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        String::keyed_classes()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,126 @@
+use crate::{Cardinality, CivilDate, ClassKey, ClassSpace, Classify, ClassifyContext};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SystemTimeClassification {
+    /// Any time before the Unix epoch
+    PreEpoch,
+    /// The Unix epoch, Jan 1st, 1970 at midnight UTC
+    Epoch,
+    /// Any other time
+    Normal,
+    /// Any time on or after the end of the plausible date range, 2050 by
+    /// default
+    FarFuture,
+}
+
+impl Classify for SystemTime {
+    type Output = SystemTimeClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(4);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        if *self == UNIX_EPOCH {
+            SystemTimeClassification::Epoch
+        } else if *self < UNIX_EPOCH {
+            SystemTimeClassification::PreEpoch
+        } else if CivilDate::from(*self) >= ctx.date_range.end.resolve(&ctx.clock) {
+            SystemTimeClassification::FarFuture
+        } else {
+            SystemTimeClassification::Normal
+        }
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl ClassSpace for SystemTime {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(4)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        [
+            SystemTimeClassification::PreEpoch,
+            SystemTimeClassification::Epoch,
+            SystemTimeClassification::Normal,
+            SystemTimeClassification::FarFuture,
+        ]
+        .into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DateRange;
+    use std::time::Duration;
+
+    const YEAR: Duration = Duration::from_secs(365 * 86_400);
+
+    #[test]
+    fn test_system_time_classify() {
+        assert_eq!(UNIX_EPOCH.classify(), SystemTimeClassification::Epoch);
+        assert_eq!(
+            SystemTime::now().classify(),
+            SystemTimeClassification::Normal
+        );
+        assert_eq!(
+            (UNIX_EPOCH - Duration::from_nanos(1)).classify(),
+            SystemTimeClassification::PreEpoch
+        );
+        assert_eq!(
+            (UNIX_EPOCH + Duration::from_nanos(1)).classify(),
+            SystemTimeClassification::Normal
+        );
+        assert_eq!(
+            (UNIX_EPOCH + 100 * YEAR).classify(),
+            SystemTimeClassification::FarFuture
+        );
+
+        // Past year 2^31, where the year no longer fits an `i32`
+        let far = UNIX_EPOCH.checked_add(4_000_000_000 * YEAR).unwrap();
+        assert_eq!(CivilDate::from(far), CivilDate::new(i32::MAX, 12, 31));
+        assert_eq!(far.classify(), SystemTimeClassification::FarFuture);
+
+        let ctx = ClassifyContext {
+            date_range: DateRange::years(1900, 2100),
+            ..ClassifyContext::DEFAULT
+        };
+        assert_eq!(
+            (UNIX_EPOCH + 100 * YEAR).classify_with(&ctx),
+            SystemTimeClassification::Normal
+        );
+    }
+
+    #[test]
+    fn test_system_time_dates() {
+        assert_eq!(CivilDate::from(UNIX_EPOCH), CivilDate::new(1970, 1, 1));
+        assert_eq!(
+            CivilDate::from(UNIX_EPOCH - Duration::from_nanos(1)),
+            CivilDate::new(1969, 12, 31)
+        );
+        assert_eq!(
+            CivilDate::from(UNIX_EPOCH - Duration::from_secs(86_400)),
+            CivilDate::new(1969, 12, 31)
+        );
+        assert_eq!(
+            CivilDate::from(UNIX_EPOCH - Duration::new(86_400, 1)),
+            CivilDate::new(1969, 12, 30)
+        );
+    }
+}
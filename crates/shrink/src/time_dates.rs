@@ -0,0 +1,262 @@
+use crate::{
+    CalendarClassification, CalendarClassify, CalendarZone, Cardinality, CivilDate, ClassKey,
+    ClassSpace, Classify, ClassifyContext, NaiveDateClassification, NaiveDateTimeClassification,
+};
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+impl From<Date> for CivilDate {
+    fn from(date: Date) -> Self {
+        CivilDate::new(
+            date.year(),
+            u8::from(date.month()).into(),
+            date.day().into(),
+        )
+    }
+}
+
+impl Classify for Date {
+    type Output = NaiveDateClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        NaiveDateClassification::of(CivilDate::from(*self), ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl ClassSpace for Date {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(3)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        NaiveDateClassification::ALL.into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+impl Classify for PrimitiveDateTime {
+    type Output = NaiveDateTimeClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let date = CivilDate::from(self.date());
+        NaiveDateTimeClassification::of(date, self.time() == Time::MIDNIGHT, ctx)
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl ClassSpace for PrimitiveDateTime {
+    fn cardinality() -> Cardinality {
+        Cardinality::Finite(3)
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        NaiveDateTimeClassification::ALL.into_iter()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        Self::classes().map(|class| (class, ClassKey::debug(&class)))
+    }
+}
+
+impl Classify for OffsetDateTime {
+    type Output = NaiveDateTimeClassification;
+
+    const MAX_CLASSES: Option<u128> = Some(3);
+
+    fn classify(&self) -> Self::Output {
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        // The UTC date comes from the timestamp, since converting to UTC
+        // panics for local times near `Date::MAX` and `Date::MIN`
+        let seconds = self.unix_timestamp();
+        NaiveDateTimeClassification::of_zoned(
+            CivilDate::from_unix_days(seconds.div_euclid(86_400)),
+            seconds.rem_euclid(86_400) == 0 && self.nanosecond() == 0,
+            CivilDate::from(self.date()),
+            ctx,
+        )
+    }
+
+    fn class_key(&self) -> ClassKey {
+        ClassKey::debug(&self.classify())
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::debug(&self.classify_with(ctx))
+    }
+}
+
+impl ClassSpace for OffsetDateTime {
+    fn cardinality() -> Cardinality {
+        PrimitiveDateTime::cardinality()
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        PrimitiveDateTime::classes()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        PrimitiveDateTime::keyed_classes()
+    }
+}
+
+impl CalendarClassify for Date {
+    const ZONES: &'static [CalendarZone] = &[CalendarZone::Naive];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        CalendarClassification::of(CivilDate::from(*self), CalendarZone::Naive)
+    }
+}
+
+impl CalendarClassify for PrimitiveDateTime {
+    const ZONES: &'static [CalendarZone] = &[CalendarZone::Naive];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        self.date().classify_calendar()
+    }
+}
+
+/// An `OffsetDateTime` only knows its own offset, so it is never in a
+/// `DstTransition`
+impl CalendarClassify for OffsetDateTime {
+    const ZONES: &'static [CalendarZone] = &[CalendarZone::Utc, CalendarZone::Offset];
+
+    fn classify_calendar(&self) -> CalendarClassification {
+        let zone = match self.offset().is_utc() {
+            true => CalendarZone::Utc,
+            false => CalendarZone::Offset,
+        };
+        CalendarClassification::of(CivilDate::from(self.date()), zone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Calendar, CalendarDay, DateRange};
+    use time::{Duration, Month, UtcOffset};
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::from_calendar_date(year, Month::try_from(month).unwrap(), day).unwrap()
+    }
+
+    #[test]
+    fn test_date_classify() {
+        assert_eq!(
+            date(1970, 1, 1).classify(),
+            NaiveDateClassification::Default
+        );
+        assert_eq!(
+            date(2023, 7, 26).classify(),
+            NaiveDateClassification::Normal
+        );
+        assert_eq!(
+            date(1969, 12, 31).classify(),
+            NaiveDateClassification::ProbablyError
+        );
+        assert_eq!(
+            date(2050, 1, 1).classify(),
+            NaiveDateClassification::ProbablyError
+        );
+
+        let ctx = ClassifyContext {
+            date_range: DateRange::years(1900, 2100),
+            ..ClassifyContext::DEFAULT
+        };
+        assert_eq!(
+            date(1955, 4, 2).classify_with(&ctx),
+            NaiveDateClassification::Normal
+        );
+    }
+
+    #[test]
+    fn test_date_time_classify() {
+        let epoch = PrimitiveDateTime::new(date(1970, 1, 1), Time::MIDNIGHT);
+        assert_eq!(epoch.classify(), NaiveDateTimeClassification::Default);
+        assert_eq!(
+            (epoch + Duration::seconds(1)).classify(),
+            NaiveDateTimeClassification::Normal
+        );
+        assert_eq!(
+            OffsetDateTime::UNIX_EPOCH.classify(),
+            NaiveDateTimeClassification::Default
+        );
+        assert_eq!(
+            OffsetDateTime::now_utc().classify(),
+            NaiveDateTimeClassification::Normal
+        );
+
+        // Midnight in UTC is still the epoch at another offset, and the
+        // instant decides whether it is before the range
+        let west = UtcOffset::from_hms(-1, 0, 0).unwrap();
+        let east = UtcOffset::from_hms(1, 0, 0).unwrap();
+        assert_eq!(
+            OffsetDateTime::UNIX_EPOCH.to_offset(west).classify(),
+            NaiveDateTimeClassification::Default
+        );
+        assert_eq!(
+            (OffsetDateTime::UNIX_EPOCH + Duration::minutes(30))
+                .to_offset(west)
+                .classify(),
+            NaiveDateTimeClassification::Normal
+        );
+        assert_eq!(
+            (OffsetDateTime::UNIX_EPOCH - Duration::minutes(30))
+                .to_offset(east)
+                .classify(),
+            NaiveDateTimeClassification::ProbablyError
+        );
+
+        // Later than the last date in UTC
+        let last = Date::MAX.with_hms(23, 30, 0).unwrap().assume_offset(west);
+        assert_eq!(last.classify(), NaiveDateTimeClassification::ProbablyError);
+    }
+
+    #[test]
+    fn test_calendar_classify() {
+        assert_eq!(
+            Calendar(date(2024, 2, 29)).classify().day,
+            CalendarDay::LeapDay
+        );
+
+        let east = UtcOffset::from_hms(2, 0, 0).unwrap();
+        let utc = OffsetDateTime::UNIX_EPOCH;
+        assert_eq!(Calendar(utc).classify().zone, CalendarZone::Utc);
+        assert_eq!(
+            Calendar(utc.to_offset(east)).classify().zone,
+            CalendarZone::Offset
+        );
+        assert_eq!(Calendar::<OffsetDateTime>::MAX_CLASSES, Some(24));
+    }
+}
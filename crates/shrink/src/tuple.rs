@@ -1,51 +1,211 @@
-use crate::Classify;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use crate::class_space::{max_classes_product, HashProduct};
+use crate::{Cardinality, ClassKey, ClassSpace, Classify, ClassifyContext};
+use crate::{StableHash, StableHasher};
+use std::hash::Hasher;
 
 impl<T: Classify> Classify for (T,) {
     type Output = u64;
 
+    const MAX_CLASSES: Option<u128> = T::MAX_CLASSES;
+
     fn classify(&self) -> Self::Output {
-        let mut hasher = DefaultHasher::new();
-        self.0.classify().hash(&mut hasher);
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let mut hasher = StableHasher::new();
+        self.0.classify_with(ctx).stable_hash(&mut hasher);
         hasher.finish()
     }
+
+    fn class_key(&self) -> ClassKey {
+        self.class_key_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::Tuple(vec![self.0.class_key_with(ctx)])
+    }
 }
 
 impl<T: Classify, U: Classify> Classify for (T, U) {
     type Output = u64;
 
+    const MAX_CLASSES: Option<u128> = max_classes_product(T::MAX_CLASSES, U::MAX_CLASSES);
+
     fn classify(&self) -> Self::Output {
-        let mut hasher = DefaultHasher::new();
-        self.0.classify().hash(&mut hasher);
-        self.1.classify().hash(&mut hasher);
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let mut hasher = StableHasher::new();
+        self.0.classify_with(ctx).stable_hash(&mut hasher);
+        self.1.classify_with(ctx).stable_hash(&mut hasher);
         hasher.finish()
     }
+
+    fn class_key(&self) -> ClassKey {
+        self.class_key_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::Tuple(vec![self.0.class_key_with(ctx), self.1.class_key_with(ctx)])
+    }
 }
 
 impl<T: Classify, U: Classify, V: Classify> Classify for (T, U, V) {
     type Output = u64;
 
+    const MAX_CLASSES: Option<u128> = max_classes_product(
+        max_classes_product(T::MAX_CLASSES, U::MAX_CLASSES),
+        V::MAX_CLASSES,
+    );
+
     fn classify(&self) -> Self::Output {
-        let mut hasher = DefaultHasher::new();
-        self.0.classify().hash(&mut hasher);
-        self.1.classify().hash(&mut hasher);
-        self.2.classify().hash(&mut hasher);
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let mut hasher = StableHasher::new();
+        self.0.classify_with(ctx).stable_hash(&mut hasher);
+        self.1.classify_with(ctx).stable_hash(&mut hasher);
+        self.2.classify_with(ctx).stable_hash(&mut hasher);
         hasher.finish()
     }
+
+    fn class_key(&self) -> ClassKey {
+        self.class_key_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::Tuple(vec![
+            self.0.class_key_with(ctx),
+            self.1.class_key_with(ctx),
+            self.2.class_key_with(ctx),
+        ])
+    }
 }
 
 impl<T: Classify, U: Classify, V: Classify, W: Classify> Classify for (T, U, V, W) {
     type Output = u64;
 
+    const MAX_CLASSES: Option<u128> = max_classes_product(
+        max_classes_product(T::MAX_CLASSES, U::MAX_CLASSES),
+        max_classes_product(V::MAX_CLASSES, W::MAX_CLASSES),
+    );
+
     fn classify(&self) -> Self::Output {
-        let mut hasher = DefaultHasher::new();
-        self.0.classify().hash(&mut hasher);
-        self.1.classify().hash(&mut hasher);
-        self.2.classify().hash(&mut hasher);
-        self.3.classify().hash(&mut hasher);
+        self.classify_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn classify_with(&self, ctx: &ClassifyContext) -> Self::Output {
+        let mut hasher = StableHasher::new();
+        self.0.classify_with(ctx).stable_hash(&mut hasher);
+        self.1.classify_with(ctx).stable_hash(&mut hasher);
+        self.2.classify_with(ctx).stable_hash(&mut hasher);
+        self.3.classify_with(ctx).stable_hash(&mut hasher);
         hasher.finish()
     }
+
+    fn class_key(&self) -> ClassKey {
+        self.class_key_with(&ClassifyContext::DEFAULT)
+    }
+
+    fn class_key_with(&self, ctx: &ClassifyContext) -> ClassKey {
+        ClassKey::Tuple(vec![
+            self.0.class_key_with(ctx),
+            self.1.class_key_with(ctx),
+            self.2.class_key_with(ctx),
+            self.3.class_key_with(ctx),
+        ])
+    }
+}
+
+impl<T: ClassSpace> ClassSpace for (T,) {
+    fn cardinality() -> Cardinality {
+        T::cardinality()
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        HashProduct::new().classes::<T>().finish()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        HashProduct::new()
+            .classes::<T>()
+            .finish_keyed()
+            .map(|(class, keys)| (class, ClassKey::Tuple(keys)))
+    }
+}
+
+impl<T: ClassSpace, U: ClassSpace> ClassSpace for (T, U) {
+    fn cardinality() -> Cardinality {
+        T::cardinality().product(U::cardinality())
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        HashProduct::new().classes::<T>().classes::<U>().finish()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        HashProduct::new()
+            .classes::<T>()
+            .classes::<U>()
+            .finish_keyed()
+            .map(|(class, keys)| (class, ClassKey::Tuple(keys)))
+    }
+}
+
+impl<T: ClassSpace, U: ClassSpace, V: ClassSpace> ClassSpace for (T, U, V) {
+    fn cardinality() -> Cardinality {
+        T::cardinality()
+            .product(U::cardinality())
+            .product(V::cardinality())
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        HashProduct::new()
+            .classes::<T>()
+            .classes::<U>()
+            .classes::<V>()
+            .finish()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        HashProduct::new()
+            .classes::<T>()
+            .classes::<U>()
+            .classes::<V>()
+            .finish_keyed()
+            .map(|(class, keys)| (class, ClassKey::Tuple(keys)))
+    }
+}
+
+impl<T: ClassSpace, U: ClassSpace, V: ClassSpace, W: ClassSpace> ClassSpace for (T, U, V, W) {
+    fn cardinality() -> Cardinality {
+        T::cardinality()
+            .product(U::cardinality())
+            .product(V::cardinality())
+            .product(W::cardinality())
+    }
+
+    fn classes() -> impl Iterator<Item = Self::Output> {
+        HashProduct::new()
+            .classes::<T>()
+            .classes::<U>()
+            .classes::<V>()
+            .classes::<W>()
+            .finish()
+    }
+
+    fn keyed_classes() -> impl Iterator<Item = (Self::Output, ClassKey)> {
+        HashProduct::new()
+            .classes::<T>()
+            .classes::<U>()
+            .classes::<V>()
+            .classes::<W>()
+            .finish_keyed()
+            .map(|(class, keys)| (class, ClassKey::Tuple(keys)))
+    }
 }
 
 #[cfg(test)]
@@ -53,6 +213,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_tuple_classification() {
         let single = (42i32,).classify();
         let pair = (42, "hello").classify();
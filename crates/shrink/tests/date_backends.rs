@@ -0,0 +1,196 @@
+//! Checks that `chrono`, `time` and `jiff` dates are classified alike
+#![cfg(all(feature = "chrono", feature = "time", feature = "jiff"))]
+
+use shrink::{Calendar, CivilDate, ClassKey, Classify, ClassifyContext, Clock, DateRange};
+
+/// A local date time and its offset from UTC in seconds
+#[derive(Debug, Copy, Clone)]
+struct Case {
+    date: (i32, u8, u8),
+    time: (u8, u8, u8),
+    offset: i32,
+}
+
+impl Case {
+    const fn new(date: (i32, u8, u8), time: (u8, u8, u8), offset: i32) -> Self {
+        Self { date, time, offset }
+    }
+}
+
+const CASES: &[Case] = &[
+    Case::new((1970, 1, 1), (0, 0, 0), 0),
+    Case::new((1970, 1, 1), (0, 0, 1), 0),
+    Case::new((1969, 12, 31), (23, 0, 0), -3600),
+    Case::new((1970, 1, 1), (1, 0, 0), 3600),
+    Case::new((1970, 1, 1), (0, 30, 0), 3600),
+    Case::new((1969, 12, 31), (23, 59, 59), 0),
+    Case::new((2024, 2, 29), (12, 0, 0), 0),
+    Case::new((2023, 12, 31), (23, 59, 59), 19800),
+    Case::new((2049, 12, 31), (23, 0, 0), -7200),
+    Case::new((2050, 1, 1), (0, 0, 0), 0),
+    Case::new((1900, 1, 1), (0, 0, 0), 0),
+    Case::new((2099, 6, 30), (8, 15, 0), -14400),
+    Case::new((1, 1, 1), (0, 0, 0), 0),
+    Case::new((9999, 6, 30), (12, 0, 0), 0),
+    Case::new((9999, 12, 31), (23, 30, 0), -3600),
+];
+
+fn contexts() -> Vec<ClassifyContext> {
+    vec![
+        ClassifyContext::DEFAULT,
+        ClassifyContext {
+            epoch: CivilDate::new(1900, 1, 1),
+            date_range: DateRange::years(1900, 2100),
+            ..ClassifyContext::DEFAULT
+        },
+        ClassifyContext {
+            date_range: DateRange::relative(100, 50),
+            clock: Clock::Fixed(CivilDate::new(2024, 5, 1)),
+            ..ClassifyContext::DEFAULT
+        },
+    ]
+}
+
+/// The classification, class key and calendar classification of a value,
+/// formatted so that backends can be compared
+fn summary<T>(value: T, ctx: &ClassifyContext) -> (String, ClassKey, String)
+where
+    T: Classify + shrink::CalendarClassify,
+    T::Output: std::fmt::Debug,
+{
+    (
+        format!("{:?}", value.classify_with(ctx)),
+        value.class_key_with(ctx),
+        format!("{:?}", Calendar(&value).classify()),
+    )
+}
+
+mod chrono_case {
+    use super::Case;
+    use chrono::TimeZone;
+
+    pub fn date(case: Case) -> chrono::NaiveDate {
+        let (year, month, day) = case.date;
+        chrono::NaiveDate::from_ymd_opt(year, month.into(), day.into()).unwrap()
+    }
+
+    pub fn date_time(case: Case) -> chrono::NaiveDateTime {
+        let (hour, minute, second) = case.time;
+        date(case)
+            .and_hms_opt(hour.into(), minute.into(), second.into())
+            .unwrap()
+    }
+
+    pub fn zoned(case: Case) -> chrono::DateTime<chrono::FixedOffset> {
+        chrono::FixedOffset::east_opt(case.offset)
+            .unwrap()
+            .from_local_datetime(&date_time(case))
+            .unwrap()
+    }
+}
+
+mod time_case {
+    use super::Case;
+
+    pub fn date(case: Case) -> time::Date {
+        let (year, month, day) = case.date;
+        time::Date::from_calendar_date(year, month.try_into().unwrap(), day).unwrap()
+    }
+
+    pub fn date_time(case: Case) -> time::PrimitiveDateTime {
+        let (hour, minute, second) = case.time;
+        date(case).with_hms(hour, minute, second).unwrap()
+    }
+
+    pub fn zoned(case: Case) -> time::OffsetDateTime {
+        let offset = time::UtcOffset::from_whole_seconds(case.offset).unwrap();
+        date_time(case).assume_offset(offset)
+    }
+}
+
+mod jiff_case {
+    use super::Case;
+
+    pub fn date(case: Case) -> jiff::civil::Date {
+        let (year, month, day) = case.date;
+        jiff::civil::date(year as i16, month as i8, day as i8)
+    }
+
+    pub fn date_time(case: Case) -> jiff::civil::DateTime {
+        let (hour, minute, second) = case.time;
+        date(case).at(hour as i8, minute as i8, second as i8, 0)
+    }
+
+    /// `None` past the range of jiff timestamps, which ends before the
+    /// last local date time
+    pub fn zoned(case: Case) -> Option<jiff::Zoned> {
+        let offset = jiff::tz::Offset::from_seconds(case.offset).unwrap();
+        jiff::tz::TimeZone::fixed(offset)
+            .to_zoned(date_time(case))
+            .ok()
+    }
+}
+
+#[test]
+fn dates_agree() {
+    for ctx in contexts() {
+        for &case in CASES {
+            let chrono = summary(chrono_case::date(case), &ctx);
+            assert_eq!(chrono, summary(time_case::date(case), &ctx), "{case:?}");
+            assert_eq!(chrono, summary(jiff_case::date(case), &ctx), "{case:?}");
+        }
+    }
+}
+
+#[test]
+fn date_times_agree() {
+    for ctx in contexts() {
+        for &case in CASES {
+            let chrono = summary(chrono_case::date_time(case), &ctx);
+            assert_eq!(
+                chrono,
+                summary(time_case::date_time(case), &ctx),
+                "{case:?}"
+            );
+            assert_eq!(
+                chrono,
+                summary(jiff_case::date_time(case), &ctx),
+                "{case:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn zoned_date_times_agree() {
+    for ctx in contexts() {
+        for &case in CASES {
+            let chrono = summary(chrono_case::zoned(case), &ctx);
+            assert_eq!(chrono, summary(time_case::zoned(case), &ctx), "{case:?}");
+            if let Some(jiff) = jiff_case::zoned(case) {
+                assert_eq!(chrono, summary(jiff, &ctx), "{case:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn timestamps_agree_with_utc_date_times() {
+    for ctx in contexts() {
+        for &case in CASES.iter().filter(|case| case.offset == 0) {
+            let utc = chrono_case::zoned(case).with_timezone(&chrono::Utc);
+            let timestamp = jiff_case::zoned(case).unwrap().timestamp();
+
+            assert_eq!(
+                utc.classify_with(&ctx),
+                timestamp.classify_with(&ctx),
+                "{case:?}"
+            );
+            assert_eq!(
+                utc.class_key_with(&ctx),
+                timestamp.class_key_with(&ctx),
+                "{case:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use syn::punctuated::Punctuated;
+use syn::{Attribute, ExprPath, LitInt, LitStr, Result, Token, WherePredicate};
+
+/// Options set with `#[classify(...)]` on the struct or enum itself
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// `#[classify(bound = "T: Classify")]`, where clause predicates used
+    /// in place of the inferred ones
+    pub(crate) bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    /// `#[classify(typed)]`, generate a `{Name}Classification` struct and
+    /// use it as the `Output` in place of `u64`
+    pub(crate) typed: bool,
+    /// `#[classify(max_classes = N)]`, fail the build if the type can have
+    /// more than `N` classifications
+    pub(crate) max_classes: Option<LitInt>,
+}
+
+impl ContainerAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in classify_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("bound") {
+                    let bound: LitStr = meta.value()?.parse()?;
+                    parsed.bound = Some(bound.parse_with(Punctuated::parse_terminated)?);
+                    Ok(())
+                } else if meta.path.is_ident("typed") {
+                    parsed.typed = true;
+                    Ok(())
+                } else if meta.path.is_ident("max_classes") {
+                    let max: LitInt = meta.value()?.parse()?;
+                    max.base10_parse::<u128>()?;
+                    parsed.max_classes = Some(max);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown classify container attribute"))
+                }
+            })?;
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Options set with `#[classify(...)]` on an enum variant
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    /// `#[classify(group = "name")]`, variants in the same group share a
+    /// single class tag
+    pub(crate) group: Option<LitStr>,
+    /// `#[classify(ignore_fields)]`, the variant is classified by its tag
+    /// alone
+    pub(crate) ignore_fields: bool,
+    /// `#[classify(id = N)]`, a fixed tag used in place of the variant name
+    pub(crate) id: Option<LitInt>,
+}
+
+impl VariantAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in classify_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("group") {
+                    parsed.group = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("ignore_fields") {
+                    parsed.ignore_fields = true;
+                    Ok(())
+                } else if meta.path.is_ident("id") {
+                    let id: LitInt = meta.value()?.parse()?;
+                    id.base10_parse::<u64>()?;
+                    parsed.id = Some(id);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown classify variant attribute"))
+                }
+            })?;
+
+            if parsed.group.is_some() && parsed.id.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "classify(group) can not be combined with classify(id)",
+                ));
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Options set with `#[classify(...)]` on a field
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// `#[classify(skip)]`, the field is ignored entirely
+    pub(crate) skip: bool,
+    /// `#[classify(with = "path::to::fn")]`, a function classifying the
+    /// field in place of `Classify::classify`
+    pub(crate) with: Option<ExprPath>,
+    /// `#[classify(date_range = "1900..2100")]`, the plausible dates of the
+    /// field, in place of the ones in the `ClassifyContext`
+    pub(crate) date_range: Option<shrink::DateRange>,
+    /// `#[classify(calendar)]`, the field is classified by its place in the
+    /// calendar with `shrink::Calendar`
+    pub(crate) calendar: bool,
+}
+
+impl FieldAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in classify_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    parsed.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("with") {
+                    let path: LitStr = meta.value()?.parse()?;
+                    parsed.with = Some(path.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("calendar") {
+                    parsed.calendar = true;
+                    Ok(())
+                } else if meta.path.is_ident("date_range") {
+                    let range: LitStr = meta.value()?.parse()?;
+                    parsed.date_range = Some(
+                        range
+                            .value()
+                            .parse()
+                            .map_err(|err| syn::Error::new_spanned(&range, err))?,
+                    );
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown classify field attribute"))
+                }
+            })?;
+
+            if parsed.skip && parsed.with.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "classify(skip) can not be combined with classify(with)",
+                ));
+            }
+            if (parsed.skip || parsed.with.is_some()) && parsed.date_range.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "classify(date_range) can not be combined with classify(skip) or classify(with)",
+                ));
+            }
+            if (parsed.skip || parsed.with.is_some() || parsed.date_range.is_some())
+                && parsed.calendar
+            {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "classify(calendar) can not be combined with classify(skip), classify(with) or classify(date_range)",
+                ));
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn classify_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("classify"))
+}
@@ -0,0 +1,73 @@
+use quote::quote;
+use std::collections::HashSet;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Generics, Ident, Token, Type, WherePredicate};
+
+/// Adds the where clause predicates the derived impl needs.
+///
+/// If the container has a `#[classify(bound = "...")]` attribute those
+/// predicates are used as-is. Otherwise a `FieldTy: Trait` predicate, for
+/// the derived `trait_path`, is inferred for every classified field type that mentions one
+/// of the type parameters. Bounding the field type, rather than the
+/// parameter, also picks up the `Output` bounds that containers such as
+/// `Vec<T>` need. Field types paired with a trait of their own, such as
+/// `#[classify(calendar)]` fields, are bounded by that trait instead.
+pub(crate) fn add_bounds<'a>(
+    generics: &Generics,
+    explicit: Option<&Punctuated<WherePredicate, Token![,]>>,
+    field_types: impl IntoIterator<Item = (&'a Type, Option<syn::Path>)>,
+    trait_path: &syn::Path,
+) -> Generics {
+    let mut generics = generics.clone();
+
+    let predicates: Vec<WherePredicate> = match explicit {
+        Some(explicit) => explicit.iter().cloned().collect(),
+        None => {
+            let params: HashSet<&Ident> = generics.type_params().map(|p| &p.ident).collect();
+
+            let mut seen = HashSet::new();
+            field_types
+                .into_iter()
+                .filter(|(ty, _)| mentions_params(ty, &params))
+                .map(|(ty, field_trait)| {
+                    let field_trait = field_trait.unwrap_or_else(|| trait_path.clone());
+                    syn::parse_quote!(#ty: #field_trait)
+                })
+                .filter(|predicate: &WherePredicate| seen.insert(quote!(#predicate).to_string()))
+                .collect()
+        }
+    };
+
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+fn mentions_params(ty: &Type, params: &HashSet<&Ident>) -> bool {
+    struct Finder<'a> {
+        params: &'a HashSet<&'a Ident>,
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for Finder<'_> {
+        fn visit_path(&mut self, path: &'ast syn::Path) {
+            if let Some(first) = path.segments.first() {
+                if self.params.contains(&first.ident) {
+                    self.found = true;
+                }
+            }
+            visit::visit_path(self, path);
+        }
+    }
+
+    if params.is_empty() {
+        return false;
+    }
+
+    let mut finder = Finder {
+        params,
+        found: false,
+    };
+    finder.visit_type(ty);
+    finder.found
+}
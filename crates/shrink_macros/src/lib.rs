@@ -1,152 +1,1005 @@
+mod attr;
+mod bound;
+
+use attr::{ContainerAttrs, FieldAttrs, VariantAttrs};
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
+use syn::{parse_macro_input, Data, DataEnum, DataStruct, DeriveInput, Fields};
 
-#[proc_macro_derive(Classify)]
+/// Derives `Classify` for structs and enums.
+///
+/// Enums get the same classification as `#[derive(ClassifyEnum)]`. A
+/// variant marked `#[classify(id = N)]` is tagged with that number in place
+/// of its name, and two variants can not share an id:
+///
+/// ```compile_fail
+/// #[derive(shrink_macros::Classify)]
+/// enum Shape {
+///     #[classify(id = 1)]
+///     Circle,
+///     #[classify(id = 1)]
+///     Square,
+/// }
+/// ```
+///
+/// Fields
+/// marked `#[classify(skip)]` are ignored, and fields marked
+/// `#[classify(with = "path::to::fn")]` are classified by that function,
+/// whose result must implement `shrink::StableHash`.
+/// The derived `classify_with` passes its `ClassifyContext` on to every
+/// other field. `#[classify(date_range = "1900..2100")]` on a field
+/// replaces the range of plausible dates for that field, see
+/// `shrink::DateRange` for the syntax. Invalid dates and empty ranges fail
+/// the build:
+///
+/// ```compile_fail
+/// #[derive(shrink_macros::Classify)]
+/// struct Event {
+///     #[classify(date_range = "2100..1900")]
+///     on: chrono::NaiveDate,
+/// }
+/// ```
+///
+/// `#[classify(calendar)]` classifies a date field with `shrink::Calendar`.
+///
+/// For generic types a `FieldTy: Classify` bound is inferred for every field
+/// that uses a type parameter. `#[classify(bound = "...")]` on the type
+/// replaces the inferred bounds.
+///
+/// Every impl sets `MAX_CLASSES` from the field types. With
+/// `#[classify(max_classes = N)]` on the type, the build fails if that is
+/// more than `N`, or unbounded:
+///
+/// ```compile_fail
+/// // 3 * 3 classes
+/// #[derive(shrink_macros::Classify)]
+/// #[classify(max_classes = 8)]
+/// struct Pair {
+///     first: u8,
+///     second: String,
+/// }
+/// ```
+///
+/// Generic types are only checked once their parameters are known, when an
+/// instantiation is classified. The check is part of monomorphization, so
+/// `cargo check` does not report it but `cargo build` does:
+///
+/// ```compile_fail
+/// use shrink::Classify;
+///
+/// // 1 + 3 classes with `T = u8`
+/// #[derive(shrink_macros::Classify)]
+/// #[classify(max_classes = 3)]
+/// struct Wrapper<T> {
+///     inner: Option<T>,
+/// }
+///
+/// Wrapper { inner: Some(1u8) }.classify();
+/// ```
+///
+/// `#[classify(typed)]` on a struct generates a `{Name}Classification`
+/// struct holding the classification of each field, and uses it as the
+/// `Output` in place of an opaque `u64`.
+#[proc_macro_derive(Classify, attributes(classify))]
 pub fn derive_classify(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
 
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let expanded = match &input.data {
+        Data::Struct(data) => expand_struct(&input, data),
+        Data::Enum(data) => expand_enum(&input, data),
+        Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "Classify can not be derived for unions",
+        )),
+    };
+
+    TokenStream::from(expanded.unwrap_or_else(syn::Error::into_compile_error))
+}
+
+#[proc_macro_derive(ClassifyEnum, attributes(classify))]
+pub fn classify_enum_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let expanded = match &input.data {
+        Data::Enum(data) => expand_enum(&input, data),
+        Data::Struct(data) => Err(syn::Error::new_spanned(
+            data.struct_token,
+            "ClassifyEnum can only be derived for enums",
+        )),
+        Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "ClassifyEnum can only be derived for enums",
+        )),
+    };
+
+    TokenStream::from(expanded.unwrap_or_else(syn::Error::into_compile_error))
+}
+
+/// Derives `ClassSpace` for a type that also derives `Classify`, listing
+/// the classifications the derived `classify` can return.
+///
+/// It reads the same `#[classify(...)]` attributes as `Classify`. A field
+/// with a custom classifier makes the whole type unbounded, as the values
+/// that function can return are unknown.
+#[proc_macro_derive(ClassSpace, attributes(classify))]
+pub fn derive_class_space(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let expanded = match &input.data {
+        Data::Struct(data) => expand_struct_space(&input, data),
+        Data::Enum(data) => expand_enum_space(&input, data),
+        Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "ClassSpace can not be derived for unions",
+        )),
+    };
 
-    let classify_body = generate_classify_body(&input.data);
+    TokenStream::from(expanded.unwrap_or_else(syn::Error::into_compile_error))
+}
+
+/// A field of a struct or enum variant, bound by reference when `self` is
+/// destructured
+struct BoundField<'a> {
+    field: &'a syn::Field,
+    member: syn::Member,
+    binding: syn::Ident,
+    attrs: FieldAttrs,
+    /// The type the field is classified as: `shrink::Calendar<FieldTy>`
+    /// with `#[classify(calendar)]`, the field type otherwise
+    ty: syn::Type,
+}
+
+fn bind_fields(fields: &Fields) -> syn::Result<Vec<BoundField<'_>>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(syn::Index::from(i)),
+            };
+
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            let ty = &field.ty;
+            let ty = match attrs.calendar {
+                true => syn::parse_quote!(shrink::Calendar<#ty>),
+                false => ty.clone(),
+            };
+
+            Ok(BoundField {
+                field,
+                member,
+                binding: format_ident!("__field{}", i),
+                attrs,
+                ty,
+            })
+        })
+        .collect()
+}
+
+/// The fields that take part in classification
+fn classified<'a, 'b>(fields: &'b [BoundField<'a>]) -> impl Iterator<Item = &'b BoundField<'a>> {
+    fields.iter().filter(|f| !f.attrs.skip)
+}
+
+/// The types of the fields classified with `Classify`, which may need a
+/// bound. `#[classify(calendar)]` fields need `shrink::CalendarClassify`.
+fn bounded_types<'a>(
+    fields: &'a [BoundField<'a>],
+) -> impl Iterator<Item = (&'a syn::Type, Option<syn::Path>)> {
+    classified(fields)
+        .filter(|f| f.attrs.with.is_none())
+        .map(|f| match f.attrs.calendar {
+            true => (
+                &f.field.ty,
+                Some(syn::parse_quote!(shrink::CalendarClassify)),
+            ),
+            false => (&f.ty, None),
+        })
+}
+
+/// A `{ member: binding, .. }` pattern binding every classified field
+fn fields_pattern(fields: &[BoundField]) -> proc_macro2::TokenStream {
+    let bindings = classified(fields).map(|f| {
+        let member = &f.member;
+        let binding = &f.binding;
+        quote! { #member: #binding }
+    });
+
+    quote! { { #(#bindings,)* .. } }
+}
+
+/// An expression classifying a single field, either with `Classify` and
+/// the `ctx` of the derived `classify_with`, or with the function given by
+/// `#[classify(with = "...")]`
+fn classify_field(f: &BoundField) -> proc_macro2::TokenStream {
+    let binding = field_value(f);
+    let ctx = field_context(f);
+    match &f.attrs.with {
+        Some(with) => quote! { #with(#binding) },
+        None => quote! { shrink::Classify::classify_with(#binding, #ctx) },
+    }
+}
+
+/// The field's binding, wrapped in `shrink::Calendar` with
+/// `#[classify(calendar)]`
+fn field_value(f: &BoundField) -> proc_macro2::TokenStream {
+    let binding = &f.binding;
+    match f.attrs.calendar {
+        true => quote! { &shrink::Calendar(#binding) },
+        false => quote! { #binding },
+    }
+}
+
+/// The context a field is classified with: `ctx`, with the field's
+/// `#[classify(date_range = "...")]` if it has one
+fn field_context(f: &BoundField) -> proc_macro2::TokenStream {
+    let Some(range) = &f.attrs.date_range else {
+        return quote! { ctx };
+    };
+
+    let bound = |bound: &shrink::DateBound| match bound {
+        shrink::DateBound::Date(shrink::CivilDate { year, month, day }) => quote! {
+            shrink::DateBound::Date(shrink::CivilDate::new(#year, #month, #day))
+        },
+        shrink::DateBound::YearsFromToday(years) => quote! {
+            shrink::DateBound::YearsFromToday(#years)
+        },
+    };
+    let (start, end) = (bound(&range.start), bound(&range.end));
+
+    quote! {
+        &shrink::ClassifyContext {
+            date_range: shrink::DateRange { start: #start, end: #end },
+            ..ctx.clone()
+        }
+    }
+}
+
+/// An expression describing a single field as a `ClassKey`. Fields
+/// classified by a custom function are described by their stable hash.
+fn field_class_key(f: &BoundField) -> proc_macro2::TokenStream {
+    let binding = &f.binding;
+    match &f.attrs.with {
+        Some(with) => quote! { shrink::ClassKey::Opaque(shrink::stable_hash(&#with(#binding))) },
+        None => {
+            let value = field_value(f);
+            let ctx = field_context(f);
+            quote! { shrink::Classify::class_key_with(#value, #ctx) }
+        }
+    }
+}
+
+/// Statements hashing the classification of every classified field.
+///
+/// Named fields are hashed in name order along with their names, so
+/// reordering the fields of a type does not change its keys.
+fn hash_fields(fields: &[BoundField]) -> proc_macro2::TokenStream {
+    let hashes = hash_order(fields).into_iter().map(|f| {
+        let classification = classify_field(f);
+        match &f.field.ident {
+            Some(name) => quote! {
+                shrink::StableHash::stable_hash(stringify!(#name), &mut hasher);
+                shrink::StableHash::stable_hash(&#classification, &mut hasher);
+            },
+            None => quote! {
+                shrink::StableHash::stable_hash(&#classification, &mut hasher);
+            },
+        }
+    });
+
+    quote! { #(#hashes)* }
+}
+
+/// The classified fields in the order they are hashed: by name for named
+/// fields, by position otherwise
+fn hash_order<'a, 'b>(fields: &'b [BoundField<'a>]) -> Vec<&'b BoundField<'a>> {
+    let mut fields: Vec<&BoundField> = classified(fields).collect();
+    fields.sort_by_cached_key(|f| f.field.ident.as_ref().map(ToString::to_string));
+    fields
+}
+
+/// The number of classifications of the classified fields taken together
+fn fields_cardinality(fields: &[BoundField]) -> proc_macro2::TokenStream {
+    let factors = classified(fields).map(|f| {
+        let ty = &f.ty;
+        match &f.attrs.with {
+            Some(_) => quote! { shrink::Cardinality::Unbounded },
+            None => quote! { <#ty as shrink::ClassSpace>::cardinality() },
+        }
+    });
 
-    let expanded = quote! {
+    quote! { shrink::Cardinality::Finite(1) #(.product(#factors))* }
+}
+
+/// A `HashProduct` making the same writes as [`hash_fields`] for every
+/// combination of field classifications
+fn hash_product(fields: &[BoundField]) -> proc_macro2::TokenStream {
+    let steps = hash_order(fields).into_iter().map(|f| {
+        let ty = &f.ty;
+        let classes = match &f.attrs.with {
+            Some(_) => quote! { .unbounded() },
+            None => quote! { .classes::<#ty>() },
+        };
+        match &f.field.ident {
+            Some(name) => quote! { .write(stringify!(#name)) #classes },
+            None => classes,
+        }
+    });
+
+    quote! { #(#steps)* }
+}
+
+/// `MAX_CLASSES` of the classified fields taken together
+fn fields_max_classes(fields: &[BoundField]) -> proc_macro2::TokenStream {
+    classified(fields).fold(quote! { Some(1) }, |max, f| {
+        let ty = &f.ty;
+        let field_max = match &f.attrs.with {
+            Some(_) => quote! { None },
+            None => quote! { <#ty as shrink::Classify>::MAX_CLASSES },
+        };
+        quote! { shrink::max_classes_product(#max, #field_max) }
+    })
+}
+
+/// The `MAX_CLASSES` item of a derived impl, asserting that it is within
+/// the `#[classify(max_classes = N)]` budget if there is one
+fn max_classes_const(
+    name: &syn::Ident,
+    max_classes: proc_macro2::TokenStream,
+    budget: Option<&syn::LitInt>,
+) -> proc_macro2::TokenStream {
+    match budget {
+        Some(budget) => quote! {
+            const MAX_CLASSES: Option<u128> = {
+                let max = #max_classes;
+                assert!(
+                    matches!(max, Some(max) if max <= #budget),
+                    concat!(
+                        "`",
+                        stringify!(#name),
+                        "` can have more classifications than its max_classes budget of ",
+                        stringify!(#budget),
+                    ),
+                );
+                max
+            };
+        },
+        None => quote! {
+            const MAX_CLASSES: Option<u128> = #max_classes;
+        },
+    }
+}
+
+/// Forces the `MAX_CLASSES` budget check to be evaluated.
+///
+/// Non-generic types are checked where they are defined. Generic types can
+/// only be checked once the parameters are known, so they are checked by
+/// every instantiation that gets classified. Returns the statement for the
+/// body of `classify` and the item to follow the impl.
+fn budget_check(
+    input: &DeriveInput,
+    attrs: &ContainerAttrs,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let name = &input.ident;
+    match &attrs.max_classes {
+        None => (quote! {}, quote! {}),
+        Some(_) if input.generics.params.is_empty() => (
+            quote! {},
+            quote! { const _: Option<u128> = <#name as shrink::Classify>::MAX_CLASSES; },
+        ),
+        Some(_) => (
+            quote! { let _ = <Self as shrink::Classify>::MAX_CLASSES; },
+            quote! {},
+        ),
+    }
+}
+
+/// A `ClassKey` describing every classified field
+fn fields_class_key(fields: &Fields, bound: &[BoundField]) -> proc_macro2::TokenStream {
+    let keys = classified(bound).map(|f| {
+        let key = field_class_key(f);
+        match &f.field.ident {
+            Some(name) => quote! {
+                (stringify!(#name).to_string(), #key)
+            },
+            None => key,
+        }
+    });
+
+    match fields {
+        Fields::Named(_) => quote! { shrink::ClassKey::Struct(vec![#(#keys),*]) },
+        Fields::Unnamed(_) | Fields::Unit => quote! { shrink::ClassKey::Tuple(vec![#(#keys),*]) },
+    }
+}
+
+fn expand_struct(input: &DeriveInput, data: &DataStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+
+    let fields = bind_fields(&data.fields)?;
+
+    let generics = bound::add_bounds(
+        &input.generics,
+        attrs.bound.as_ref(),
+        bounded_types(&fields),
+        &syn::parse_quote!(shrink::Classify),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let pattern = fields_pattern(&fields);
+    let hashes = hash_fields(&fields);
+    let class_key = fields_class_key(&data.fields, &fields);
+    let max_classes = max_classes_const(
+        name,
+        fields_max_classes(&fields),
+        attrs.max_classes.as_ref(),
+    );
+    let (check, checked) = budget_check(input, &attrs);
+
+    if attrs.typed {
+        if !input.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.generics,
+                "classify(typed) is not supported on generic types",
+            ));
+        }
+
+        return Ok(expand_typed_struct(
+            input,
+            data,
+            &fields,
+            class_key,
+            max_classes,
+            checked,
+        ));
+    }
+
+    Ok(quote! {
         impl #impl_generics shrink::Classify for #name #ty_generics #where_clause {
             type Output = u64;
 
+            #max_classes
+
             fn classify(&self) -> Self::Output {
-                use std::hash::{Hash, Hasher};
-                use std::collections::hash_map::DefaultHasher;
+                shrink::Classify::classify_with(self, &shrink::ClassifyContext::DEFAULT)
+            }
+
+            fn classify_with(&self, ctx: &shrink::ClassifyContext) -> Self::Output {
+                use std::hash::Hasher;
 
-                let mut hasher = DefaultHasher::new();
-                #classify_body
+                #check
+                let Self #pattern = self;
+                let mut hasher = shrink::StableHasher::new();
+                #hashes
                 hasher.finish()
             }
+
+            fn class_key(&self) -> shrink::ClassKey {
+                shrink::Classify::class_key_with(self, &shrink::ClassifyContext::DEFAULT)
+            }
+
+            fn class_key_with(&self, ctx: &shrink::ClassifyContext) -> shrink::ClassKey {
+                let Self #pattern = self;
+                #class_key
+            }
+        }
+
+        #checked
+    })
+}
+
+/// Generates a `{Name}Classification` struct with one field per classified
+/// field, holding that field's classification, and uses it as the `Output`.
+/// Fields with a custom classifier hold the stable hash of its result.
+fn expand_typed_struct(
+    input: &DeriveInput,
+    data: &DataStruct,
+    fields: &[BoundField],
+    class_key: proc_macro2::TokenStream,
+    max_classes: proc_macro2::TokenStream,
+    checked: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let output = format_ident!("{}Classification", name);
+    let pattern = fields_pattern(fields);
+
+    let types = classified(fields).map(|f| {
+        let ty = &f.ty;
+        match &f.attrs.with {
+            Some(_) => quote! { u64 },
+            None => quote! { <#ty as shrink::Classify>::Output },
+        }
+    });
+    let values = classified(fields).map(|f| match &f.attrs.with {
+        Some(_) => {
+            let classification = classify_field(f);
+            quote! { shrink::stable_hash(&#classification) }
+        }
+        None => classify_field(f),
+    });
+
+    let (definition, construction) = match &data.fields {
+        Fields::Named(_) => {
+            let names: Vec<_> = classified(fields).map(|f| &f.field.ident).collect();
+            (
+                quote! { { #(pub #names: #types,)* } },
+                quote! { #output { #(#names: #values,)* } },
+            )
+        }
+        Fields::Unnamed(_) => (
+            quote! { ( #(pub #types,)* ); },
+            quote! { #output( #(#values,)* ) },
+        ),
+        Fields::Unit => (quote! { ; }, quote! { #output }),
+    };
+
+    let stable_hashes = match &data.fields {
+        Fields::Named(_) => {
+            let names = classified(fields).map(|f| &f.field.ident);
+            quote! { #(shrink::StableHash::stable_hash(&self.#names, state);)* }
+        }
+        Fields::Unnamed(_) => {
+            let indices = (0..classified(fields).count()).map(syn::Index::from);
+            quote! { #(shrink::StableHash::stable_hash(&self.#indices, state);)* }
         }
+        Fields::Unit => quote! { let _ = state; },
     };
 
-    TokenStream::from(expanded)
+    let doc = format!("The classification of [`{name}`], generated by `#[derive(Classify)]`");
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #vis struct #output #definition
+
+        impl shrink::StableHash for #output {
+            fn stable_hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                #stable_hashes
+            }
+        }
+
+        impl shrink::Classify for #name {
+            type Output = #output;
+
+            #max_classes
+
+            fn classify(&self) -> Self::Output {
+                shrink::Classify::classify_with(self, &shrink::ClassifyContext::DEFAULT)
+            }
+
+            fn classify_with(&self, ctx: &shrink::ClassifyContext) -> Self::Output {
+                let Self #pattern = self;
+                #construction
+            }
+
+            fn class_key(&self) -> shrink::ClassKey {
+                shrink::Classify::class_key_with(self, &shrink::ClassifyContext::DEFAULT)
+            }
+
+            fn class_key_with(&self, ctx: &shrink::ClassifyContext) -> shrink::ClassKey {
+                let Self #pattern = self;
+                #class_key
+            }
+        }
+
+        #checked
+    }
 }
 
-fn generate_classify_body(data: &Data) -> proc_macro2::TokenStream {
-    match *data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Named(ref fields) => {
-                let recurse = fields.named.iter().map(|f| {
-                    let name = &f.ident;
-                    quote! {
-                        shrink::Classify::classify(&self.#name).hash(&mut hasher);
-                    }
-                });
-                quote! {
-                    #(#recurse)*
+type BoundVariant<'a> = (&'a syn::Variant, VariantAttrs, Vec<BoundField<'a>>);
+
+fn bind_variants(data: &DataEnum) -> syn::Result<Vec<BoundVariant<'_>>> {
+    let mut ids = std::collections::HashMap::new();
+
+    data.variants
+        .iter()
+        .map(|variant| {
+            let attrs = VariantAttrs::parse(&variant.attrs)?;
+            if let Some(id) = &attrs.id {
+                if let Some(other) = ids.insert(id.base10_parse::<u64>()?, &variant.ident) {
+                    return Err(syn::Error::new_spanned(
+                        id,
+                        format!("classify(id = {id}) is already used by `{other}`"),
+                    ));
                 }
             }
-            Fields::Unnamed(ref fields) => {
-                let recurse = fields.unnamed.iter().enumerate().map(|(i, _)| {
-                    let index = syn::Index::from(i);
-                    quote! {
-                        shrink::Classify::classify(&self.#index).hash(&mut hasher);
-                    }
-                });
-                quote! {
-                    #(#recurse)*
+
+            let mut fields = bind_fields(&variant.fields)?;
+            if attrs.ignore_fields {
+                for field in &mut fields {
+                    field.attrs.skip = true;
+                }
+            }
+            Ok((variant, attrs, fields))
+        })
+        .collect()
+}
+
+/// The label and the tag a variant is classified under.
+///
+/// Variants are tagged by name, so reordering them does not change their
+/// keys. Grouped variants share the label of their group, and a tag hashed
+/// from the group name behind a `group:` prefix, which no variant name can
+/// start with, so a group never shares a tag with a variant.
+fn variant_tag(
+    variant: &syn::Variant,
+    attrs: &VariantAttrs,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let variant_name = &variant.ident;
+    let (label, hashed) = match &attrs.group {
+        Some(group) => (quote! { #group }, quote! { concat!("group:", #group) }),
+        None => (
+            quote! { stringify!(#variant_name) },
+            quote! { stringify!(#variant_name) },
+        ),
+    };
+    let tag = match &attrs.id {
+        Some(id) => quote! { (#id as u64) },
+        None => quote! { shrink::stable_hash(#hashed) },
+    };
+    (label, tag)
+}
+
+/// The variants with classes of their own.
+///
+/// Variants in the same group with the same classified fields share their
+/// classes, so only the first of them is kept.
+fn distinct_variants<'a, 'b>(variants: &'b [BoundVariant<'a>]) -> Vec<&'b BoundVariant<'a>> {
+    let mut seen = std::collections::HashSet::new();
+
+    variants
+        .iter()
+        .filter(|(variant, attrs, fields)| {
+            let (_, tag) = variant_tag(variant, attrs);
+            let mut signature = tag.to_string();
+            if !is_tag_only(variant, attrs) {
+                for f in hash_order(fields) {
+                    let member = &f.member;
+                    let ty = &f.ty;
+                    let with = &f.attrs.with;
+                    signature += &quote! { ; #member: #ty = #with }.to_string();
                 }
             }
-            Fields::Unit => quote! {},
+            seen.insert(signature)
+        })
+        .collect()
+}
+
+/// `MAX_CLASSES` of an enum, summed over its distinct variants
+fn variants_max_classes(variants: &[BoundVariant]) -> proc_macro2::TokenStream {
+    distinct_variants(variants).into_iter().fold(
+        quote! { Some(0) },
+        |max, (variant, attrs, fields)| {
+            let variant_max = if is_tag_only(variant, attrs) {
+                quote! { Some(1) }
+            } else {
+                fields_max_classes(fields)
+            };
+            quote! { shrink::max_classes_sum(#max, #variant_max) }
         },
-        Data::Enum(_) | Data::Union(_) => unimplemented!(),
-    }
+    )
 }
 
-#[proc_macro_derive(ClassifyEnum)]
-pub fn classify_enum_derive(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+/// Whether a variant is classified by its tag alone
+fn is_tag_only(variant: &syn::Variant, attrs: &VariantAttrs) -> bool {
+    matches!(variant.fields, Fields::Unit) || attrs.ignore_fields
+}
+
+/// Every variant is its own classification, keyed by its name or its
+/// `#[classify(id = N)]`, unless it shares a `#[classify(group = "...")]`
+/// with other variants. Variants with fields are further split by the
+/// classification of those fields, unless marked `#[classify(ignore_fields)]`.
+fn expand_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<proc_macro2::TokenStream> {
     let name = &input.ident;
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
 
-    let output_type = quote! { u64 };
+    if attrs.typed {
+        return Err(syn::Error::new_spanned(
+            name,
+            "classify(typed) is only supported on structs",
+        ));
+    }
+
+    let variants = bind_variants(data)?;
 
-    let classify_match_arms = match &input.data {
-        Data::Enum(enum_data) => enum_data
-            .variants
+    let generics = bound::add_bounds(
+        &input.generics,
+        attrs.bound.as_ref(),
+        variants
             .iter()
-            .enumerate()
-            .map(|(index, variant)| {
-                let variant_name = &variant.ident;
-                match &variant.fields {
-                    Fields::Unit => {
-                        quote! {
-                            #name::#variant_name => #index as u64
-                        }
-                    }
-                    Fields::Unnamed(fields) => {
-                        let field_types: Vec<&Type> =
-                            fields.unnamed.iter().map(|f| &f.ty).collect();
-                        let field_count = field_types.len();
-                        let field_names: Vec<syn::Ident> = (0..field_count)
-                            .map(|i| {
-                                syn::Ident::new(&format!("f{}", i), proc_macro2::Span::call_site())
-                            })
-                            .collect();
-
-                        quote! {
-                            #name::#variant_name(#(#field_names),*) => {
-                                let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                                (#index as u64).hash(&mut hasher);
-                                #(
-                                    shrink::Classify::classify(&#field_names).hash(&mut hasher);
-                                )*
-                                std::hash::Hasher::finish(&hasher)
-                            }
-                        }
-                    }
-                    Fields::Named(fields) => {
-                        let field_names: Vec<syn::Ident> = fields
-                            .named
-                            .iter()
-                            .enumerate()
-                            .map(|(i, f)| {
-                                f.ident
-                                    .clone()
-                                    .unwrap_or_else(|| format_ident!("field{}", i))
-                            })
-                            .collect();
-
-                        quote! {
-                            #name::#variant_name { #(#field_names),* } => {
-                                let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                                (#index as u64).hash(&mut hasher);
-                                #(
-                                    shrink::Classify::classify(&#field_names).hash(&mut hasher);
-                                )*
-                                std::hash::Hasher::finish(&hasher)
-                            }
-                        }
-                    }
+            .flat_map(|(_, _, fields)| bounded_types(fields)),
+        &syn::parse_quote!(shrink::Classify),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let max_classes = max_classes_const(
+        name,
+        variants_max_classes(&variants),
+        attrs.max_classes.as_ref(),
+    );
+    let (check, checked) = budget_check(input, &attrs);
+
+    let mut classify_match_arms = Vec::new();
+    let mut class_key_match_arms = Vec::new();
+
+    for (variant, variant_attrs, fields) in &variants {
+        let variant_name = &variant.ident;
+        let pattern = fields_pattern(fields);
+        let (label, tag) = variant_tag(variant, variant_attrs);
+        let tag_only = is_tag_only(variant, variant_attrs);
+
+        classify_match_arms.push(if tag_only {
+            quote! {
+                Self::#variant_name { .. } => #tag
+            }
+        } else {
+            let hashes = hash_fields(fields);
+            quote! {
+                Self::#variant_name #pattern => {
+                    let mut hasher = shrink::StableHasher::new();
+                    shrink::StableHash::stable_hash(&#tag, &mut hasher);
+                    #hashes
+                    std::hash::Hasher::finish(&hasher)
                 }
-            }),
-        _ => {
-            return quote! {
-                compile_error!("ClassifyEnum can only be derived for enums");
             }
-            .into()
-        }
-    };
+        });
 
-    let expanded = quote! {
-        impl shrink::Classify for #name {
-            type Output = #output_type;
+        class_key_match_arms.push(if tag_only {
+            quote! {
+                Self::#variant_name { .. } => shrink::ClassKey::unit_variant(#label)
+            }
+        } else {
+            let class_key = fields_class_key(&variant.fields, fields);
+            quote! {
+                Self::#variant_name #pattern => shrink::ClassKey::Variant(
+                    #label.to_string(),
+                    Box::new(#class_key),
+                )
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics shrink::Classify for #name #ty_generics #where_clause {
+            type Output = u64;
+
+            #max_classes
 
             fn classify(&self) -> Self::Output {
-                use std::hash::{Hash, Hasher};
-                use std::collections::hash_map::DefaultHasher;
+                shrink::Classify::classify_with(self, &shrink::ClassifyContext::DEFAULT)
+            }
+
+            fn classify_with(&self, ctx: &shrink::ClassifyContext) -> Self::Output {
+                #check
                 match self {
                     #(#classify_match_arms,)*
                 }
             }
+
+            fn class_key(&self) -> shrink::ClassKey {
+                shrink::Classify::class_key_with(self, &shrink::ClassifyContext::DEFAULT)
+            }
+
+            fn class_key_with(&self, ctx: &shrink::ClassifyContext) -> shrink::ClassKey {
+                match self {
+                    #(#class_key_match_arms,)*
+                }
+            }
+        }
+
+        #checked
+    })
+}
+
+fn expand_struct_space(
+    input: &DeriveInput,
+    data: &DataStruct,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+
+    let fields = bind_fields(&data.fields)?;
+
+    let generics = bound::add_bounds(
+        &input.generics,
+        attrs.bound.as_ref(),
+        bounded_types(&fields),
+        &syn::parse_quote!(shrink::ClassSpace),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let cardinality = fields_cardinality(&fields);
+    let keyed_classes = if attrs.typed {
+        typed_struct_classes(input, data, &fields)
+    } else {
+        let product = hash_product(&fields);
+        let class_key = product_class_key(&data.fields, &fields);
+        quote! {
+            shrink::HashProduct::new()
+                #product
+                .finish_keyed()
+                .map(|(class, keys)| (class, #class_key))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics shrink::ClassSpace for #name #ty_generics #where_clause {
+            fn cardinality() -> shrink::Cardinality {
+                #cardinality
+            }
+
+            fn classes() -> impl Iterator<Item = Self::Output> {
+                Self::keyed_classes().map(|(class, _)| class)
+            }
+
+            fn keyed_classes() -> impl Iterator<Item = (Self::Output, shrink::ClassKey)> {
+                #keyed_classes
+            }
+        }
+    })
+}
+
+/// A `ClassKey` built from the `keys` of a `HashProduct` combination.
+///
+/// The keys come in hash order, so they are bound by field first and then
+/// described in declaration order, like [`fields_class_key`].
+fn product_class_key(fields: &Fields, bound: &[BoundField]) -> proc_macro2::TokenStream {
+    let key_ident = |f: &BoundField| format_ident!("{}_key", f.binding);
+
+    let hashed: Vec<_> = hash_order(bound).into_iter().map(key_ident).collect();
+    let count = hashed.len();
+
+    let keys = classified(bound).map(|f| {
+        let key = key_ident(f);
+        match &f.field.ident {
+            Some(name) => quote! { (stringify!(#name).to_string(), #key) },
+            None => quote! { #key },
+        }
+    });
+    let class_key = match fields {
+        Fields::Named(_) => quote! { shrink::ClassKey::Struct(vec![#(#keys),*]) },
+        Fields::Unnamed(_) | Fields::Unit => quote! { shrink::ClassKey::Tuple(vec![#(#keys),*]) },
+    };
+
+    quote! {{
+        let [#(#hashed),*]: [shrink::ClassKey; #count] =
+            keys.try_into().expect("one key per classified field");
+        #class_key
+    }}
+}
+
+/// Builds every `{Name}Classification` of a `#[classify(typed)]` struct,
+/// with its class key, from the classifications of its fields
+fn typed_struct_classes(
+    input: &DeriveInput,
+    data: &DataStruct,
+    fields: &[BoundField],
+) -> proc_macro2::TokenStream {
+    let output = format_ident!("{}Classification", input.ident);
+    let fields: Vec<&BoundField> = classified(fields).collect();
+
+    if fields.iter().any(|f| f.attrs.with.is_some()) {
+        return quote! { Vec::<(Self::Output, shrink::ClassKey)>::new().into_iter() };
+    }
+
+    let items: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__class{}", i))
+        .collect();
+    let keys: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__key{}", i))
+        .collect();
+
+    let (construction, class_key) = match &data.fields {
+        Fields::Named(_) => {
+            let names: Vec<_> = fields.iter().map(|f| &f.field.ident).collect();
+            (
+                quote! { #output { #(#names: #items.clone(),)* } },
+                quote! {
+                    shrink::ClassKey::Struct(vec![
+                        #((stringify!(#names).to_string(), #keys.clone()),)*
+                    ])
+                },
+            )
         }
+        Fields::Unnamed(_) => (
+            quote! { #output( #(#items.clone(),)* ) },
+            quote! { shrink::ClassKey::Tuple(vec![#(#keys.clone(),)*]) },
+        ),
+        Fields::Unit => (
+            quote! { #output },
+            quote! { shrink::ClassKey::Tuple(Vec::new()) },
+        ),
     };
 
-    TokenStream::from(expanded)
+    // Nested lazily, like `HashProduct`: every closure owns the classes
+    // of the fields before it and hands clones to the next one
+    let mut classes = quote! { std::iter::once((#construction, #class_key)) };
+    for (i, f) in fields.iter().enumerate().rev() {
+        let ty = &f.ty;
+        let item = &items[i];
+        let key = &keys[i];
+        let outer_items = &items[..i];
+        let outer_keys = &keys[..i];
+        classes = quote! {
+            <#ty as shrink::ClassSpace>::keyed_classes().flat_map(move |(#item, #key)| {
+                #(let #outer_items = #outer_items.clone();)*
+                #(let #outer_keys = #outer_keys.clone();)*
+                #classes
+            })
+        };
+    }
+
+    quote! {
+        let bounded = <Self as shrink::ClassSpace>::cardinality() != shrink::Cardinality::Unbounded;
+        bounded.then(|| #classes).into_iter().flatten()
+    }
+}
+
+/// Variants are listed the way `expand_enum` classifies them
+fn expand_enum_space(
+    input: &DeriveInput,
+    data: &DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+
+    let variants = bind_variants(data)?;
+
+    let generics = bound::add_bounds(
+        &input.generics,
+        attrs.bound.as_ref(),
+        variants
+            .iter()
+            .flat_map(|(_, _, fields)| bounded_types(fields)),
+        &syn::parse_quote!(shrink::ClassSpace),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut cardinalities = Vec::new();
+    let mut classes = Vec::new();
+
+    for (variant, variant_attrs, fields) in distinct_variants(&variants) {
+        let (label, tag) = variant_tag(variant, variant_attrs);
+
+        if is_tag_only(variant, variant_attrs) {
+            cardinalities.push(quote! { shrink::Cardinality::Finite(1) });
+            classes.push(quote! {
+                std::iter::once((#tag, shrink::ClassKey::unit_variant(#label)))
+            });
+        } else {
+            let product = hash_product(fields);
+            let class_key = product_class_key(&variant.fields, fields);
+            cardinalities.push(fields_cardinality(fields));
+            classes.push(quote! {
+                shrink::HashProduct::new()
+                    .write(&#tag)
+                    #product
+                    .finish_keyed()
+                    .map(|(class, keys)| {
+                        let key = shrink::ClassKey::Variant(#label.to_string(), Box::new(#class_key));
+                        (class, key)
+                    })
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics shrink::ClassSpace for #name #ty_generics #where_clause {
+            fn cardinality() -> shrink::Cardinality {
+                shrink::Cardinality::Finite(0) #(.sum(#cardinalities))*
+            }
+
+            fn classes() -> impl Iterator<Item = Self::Output> {
+                Self::keyed_classes().map(|(class, _)| class)
+            }
+
+            fn keyed_classes() -> impl Iterator<Item = (Self::Output, shrink::ClassKey)> {
+                let bounded = <Self as shrink::ClassSpace>::cardinality() != shrink::Cardinality::Unbounded;
+                let classes = std::iter::empty() #(.chain(#classes))*;
+                bounded.then_some(classes).into_iter().flatten()
+            }
+        }
+    })
 }
@@ -0,0 +1,85 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use shrink::{
+    shrink, CalendarClassification, CalendarDay, CalendarWeek, CalendarZone, Cardinality,
+    ClassSpace, Classify,
+};
+use shrink_macros::{ClassSpace, Classify};
+
+#[derive(Classify, ClassSpace)]
+#[classify(typed)]
+struct Invoice {
+    #[classify(calendar)]
+    due: NaiveDate,
+    #[classify(calendar)]
+    sent: DateTime<Utc>,
+    paid: bool,
+}
+
+#[derive(Classify)]
+struct Generic<D> {
+    #[classify(calendar)]
+    on: D,
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn invoice(due: NaiveDate) -> Invoice {
+    Invoice {
+        due,
+        sent: date(2024, 5, 6).and_hms_opt(9, 0, 0).unwrap().and_utc(),
+        paid: false,
+    }
+}
+
+#[test]
+fn calendar_fields_are_classified_by_calendar() {
+    let class = invoice(date(2024, 2, 29)).classify();
+    assert_eq!(
+        class.due,
+        CalendarClassification {
+            day: CalendarDay::LeapDay,
+            week: CalendarWeek::Weekday,
+            zone: CalendarZone::Naive,
+        }
+    );
+    assert_eq!(class.sent.zone, CalendarZone::Utc);
+
+    assert_eq!(
+        invoice(date(2024, 4, 30)).class_key().to_string(),
+        "{due: {day: LastOfMonth, week: Weekday, zone: Naive}, \
+         sent: {day: Other, week: Weekday, zone: Utc}, paid: false}"
+    );
+
+    // Month ends are kept apart from other days in the same month
+    let invoices = vec![
+        invoice(date(2024, 4, 29)),
+        invoice(date(2024, 4, 30)),
+        invoice(date(2024, 4, 24)),
+    ];
+    assert_eq!(shrink(invoices).len(), 2);
+}
+
+#[test]
+fn calendar_fields_bound_the_class_space() {
+    assert_eq!(Invoice::MAX_CLASSES, Some(12 * 36 * 2));
+    assert_eq!(Invoice::cardinality(), Cardinality::Finite(12 * 36 * 2));
+    assert_eq!(Invoice::keyed_classes().count(), 12 * 36 * 2);
+
+    let invoice = invoice(date(2024, 1, 1));
+    assert!(Invoice::keyed_classes()
+        .any(|(class, key)| class == invoice.classify() && key == invoice.class_key()));
+}
+
+#[test]
+fn calendar_fields_can_be_generic() {
+    let weekend = Generic {
+        on: date(2024, 5, 4),
+    };
+    let weekday = Generic {
+        on: date(2024, 5, 3),
+    };
+    assert_ne!(weekend.classify(), weekday.classify());
+    assert_eq!(Generic::<NaiveDate>::MAX_CLASSES, Some(12));
+}
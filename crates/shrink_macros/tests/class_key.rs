@@ -0,0 +1,66 @@
+use shrink::{ClassKey, Classify};
+use shrink_macros::{Classify, ClassifyEnum};
+use std::collections::HashMap;
+
+#[derive(Classify)]
+struct Item {
+    first: u8,
+    second: String,
+    third: Option<Kind>,
+}
+
+#[derive(ClassifyEnum)]
+enum Kind {
+    A,
+    B(i8),
+    C { value: u16 },
+}
+
+#[derive(Classify)]
+struct Pair(u8, bool);
+
+fn item(first: u8, second: &str, third: Option<Kind>) -> Item {
+    Item {
+        first,
+        second: second.to_string(),
+        third,
+    }
+}
+
+#[test]
+fn derived_class_keys_are_readable() {
+    assert_eq!(
+        item(0, "a", None).class_key().to_string(),
+        "{first: Zero, second: NonEmpty, third: None}"
+    );
+    assert_eq!(
+        item(1, "", Some(Kind::A)).class_key().to_string(),
+        "{first: Positive, second: Empty, third: Some(A)}"
+    );
+    assert_eq!(
+        item(1, " ", Some(Kind::B(-1))).class_key().to_string(),
+        "{first: Positive, second: Whitespace, third: Some(B(Negative))}"
+    );
+    assert_eq!(
+        Kind::C { value: 0 }.class_key().to_string(),
+        "C {value: Zero}"
+    );
+    assert_eq!(Pair(255, true).class_key().to_string(), "(Max, true)");
+}
+
+#[test]
+fn class_keys_can_key_a_shrink_map() {
+    let items = vec![
+        item(1, "a", None),
+        item(2, "b", None),
+        item(0, "a", Some(Kind::A)),
+        item(0, "b", Some(Kind::A)),
+    ];
+
+    let mut classes: HashMap<ClassKey, Item> = HashMap::new();
+    for item in items {
+        classes.entry(item.class_key()).or_insert(item);
+    }
+
+    assert_eq!(classes.len(), 2);
+}
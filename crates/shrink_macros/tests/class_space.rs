@@ -0,0 +1,169 @@
+#![allow(dead_code)]
+
+use shrink::{Cardinality, ClassSpace};
+use shrink_macros::{ClassSpace, Classify, ClassifyEnum};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+#[derive(Classify, ClassSpace)]
+struct Test {
+    first: u8,
+    second: String,
+    #[classify(skip)]
+    id: u64,
+}
+
+#[derive(Classify, ClassSpace)]
+struct Pair(i8, Option<bool>);
+
+#[derive(Classify, ClassSpace)]
+#[classify(typed)]
+struct Typed {
+    first: u8,
+    second: bool,
+}
+
+#[derive(ClassifyEnum, ClassSpace)]
+enum Kind {
+    A,
+    B,
+    C,
+}
+
+#[derive(Classify, ClassSpace)]
+enum Message {
+    #[classify(ignore_fields)]
+    Text(String),
+    Number {
+        value: i32,
+        exact: bool,
+    },
+    #[classify(group = "control")]
+    Ping(u8),
+    #[classify(group = "control")]
+    Pong(u8),
+    #[classify(group = "control", ignore_fields)]
+    Close(u16),
+    #[classify(id = 7)]
+    Reset,
+}
+
+#[derive(Classify, ClassSpace)]
+struct Wrapper<T> {
+    inner: Option<T>,
+}
+
+#[derive(Classify, ClassSpace)]
+struct Unbounded {
+    first: u8,
+    items: Vec<u8>,
+}
+
+#[derive(Classify, ClassSpace)]
+struct Custom {
+    #[classify(with = "is_even")]
+    value: u32,
+}
+
+fn is_even(value: &u32) -> bool {
+    value.is_multiple_of(2)
+}
+
+/// Checks that the classes of `T` are distinct, match its cardinality and
+/// include the classification and class key of every value
+fn assert_space<T>(expected: u128, values: &[T])
+where
+    T: ClassSpace,
+    T::Output: Hash + Eq + Debug,
+{
+    let classes: Vec<T::Output> = T::classes().collect();
+    let unique: HashSet<&T::Output> = classes.iter().collect();
+
+    assert_eq!(T::cardinality(), Cardinality::Finite(expected));
+    assert_eq!(classes.len() as u128, expected);
+    assert_eq!(unique.len(), classes.len());
+
+    for value in values {
+        assert!(unique.contains(&value.classify()), "{:?}", value.classify());
+
+        let (_, key) = T::keyed_classes()
+            .find(|(class, _)| *class == value.classify())
+            .unwrap();
+        assert_eq!(key, value.class_key());
+    }
+}
+
+#[test]
+fn struct_class_space() {
+    assert_space(
+        9,
+        &[
+            Test {
+                first: 0,
+                second: " ".to_string(),
+                id: 1,
+            },
+            Test {
+                first: u8::MAX,
+                second: "a".to_string(),
+                id: 2,
+            },
+        ],
+    );
+    assert_space(15, &[Pair(i8::MIN, None), Pair(1, Some(true))]);
+}
+
+#[test]
+fn typed_struct_class_space() {
+    assert_space(
+        6,
+        &[
+            Typed {
+                first: 1,
+                second: false,
+            },
+            Typed {
+                first: 0,
+                second: true,
+            },
+        ],
+    );
+}
+
+#[test]
+fn enum_class_space() {
+    assert_space(3, &[Kind::A, Kind::C]);
+
+    // Text, Number (5 * 2), control with a field (3), Close, Reset
+    assert_space(
+        1 + 10 + 3 + 1 + 1,
+        &[
+            Message::Text("".to_string()),
+            Message::Number {
+                value: -1,
+                exact: true,
+            },
+            Message::Ping(0),
+            Message::Pong(3),
+            Message::Close(1),
+            Message::Reset,
+        ],
+    );
+}
+
+#[test]
+fn generic_class_space() {
+    assert_space(4, &[Wrapper { inner: Some(0u8) }, Wrapper { inner: None }]);
+    assert_space(3, &[Wrapper::<bool> { inner: None }]);
+}
+
+#[test]
+fn unbounded_class_space() {
+    assert_eq!(Unbounded::cardinality(), Cardinality::Unbounded);
+    assert_eq!(Unbounded::classes().count(), 0);
+
+    // The values a custom classifier returns are unknown
+    assert_eq!(Custom::cardinality(), Cardinality::Unbounded);
+    assert_eq!(Custom::classes().count(), 0);
+}
@@ -0,0 +1,144 @@
+use chrono::NaiveDate;
+use shrink::{
+    CivilDate, Classify, ClassifyContext, Clock, DateRange, Granularity, NaiveDateClassification,
+};
+use shrink_macros::Classify;
+
+#[derive(Classify)]
+struct Person {
+    born: NaiveDate,
+    tags: Vec<String>,
+}
+
+#[derive(Classify)]
+#[classify(typed)]
+struct Record {
+    person: Person,
+    maturity: Option<NaiveDate>,
+}
+
+#[derive(Classify)]
+enum Event {
+    Birth(Person),
+    Settled { on: NaiveDate },
+}
+
+fn date(year: i32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, 6, 1).unwrap()
+}
+
+fn historical() -> ClassifyContext {
+    ClassifyContext {
+        epoch: CivilDate::new(1900, 1, 1),
+        date_range: DateRange::years(1900, 2100),
+        ..ClassifyContext::DEFAULT
+    }
+}
+
+#[test]
+fn context_is_passed_to_nested_fields() {
+    let old = Person {
+        born: date(1955),
+        tags: vec![],
+    };
+    let recent = Person {
+        born: date(2000),
+        tags: vec![],
+    };
+
+    // 1955 is before the default epoch, but not before 1900
+    assert_ne!(old.classify(), recent.classify());
+    assert_eq!(
+        old.classify_with(&historical()),
+        recent.classify_with(&historical())
+    );
+    assert_eq!(
+        old.class_key_with(&historical()).to_string(),
+        "{born: Normal, tags: [len=0]}"
+    );
+
+    let record = Record {
+        person: old,
+        maturity: Some(date(2070)),
+    };
+    let class = record.classify_with(&historical());
+    assert_eq!(class.maturity, Some(NaiveDateClassification::Normal));
+    assert_eq!(class.person, recent.classify_with(&historical()));
+    assert_eq!(
+        record.classify().maturity,
+        Some(NaiveDateClassification::ProbablyError)
+    );
+
+    let settled = Event::Settled { on: date(2070) };
+    assert_ne!(settled.class_key(), settled.class_key_with(&historical()));
+    assert_eq!(
+        settled.class_key_with(&historical()).to_string(),
+        "Settled {on: Normal}"
+    );
+}
+
+#[test]
+fn context_is_passed_to_vec_fields() {
+    let fine = ClassifyContext {
+        granularity: Granularity::Fine,
+        ..ClassifyContext::DEFAULT
+    };
+
+    let one = Person {
+        born: date(2000),
+        tags: vec!["a".to_string(), "".to_string(), "".to_string()],
+    };
+    let two = Person {
+        born: date(2000),
+        tags: vec!["a".to_string(), "b".to_string(), "".to_string()],
+    };
+
+    assert_eq!(one.classify(), two.classify());
+    assert_ne!(one.classify_with(&fine), two.classify_with(&fine));
+    assert_eq!(
+        Event::Birth(one).classify_with(&ClassifyContext::DEFAULT),
+        Event::Birth(two).classify()
+    );
+}
+
+#[derive(Classify)]
+#[classify(typed)]
+struct Loan {
+    #[classify(date_range = "1900..2100")]
+    signed: NaiveDate,
+    #[classify(date_range = "now..now+50y")]
+    matures: NaiveDate,
+    paid: Option<NaiveDate>,
+}
+
+#[test]
+fn date_range_attribute_overrides_the_context() {
+    let loan = Loan {
+        signed: date(1955),
+        matures: date(2070),
+        paid: Some(date(1955)),
+    };
+
+    let ctx = ClassifyContext {
+        clock: Clock::Fixed(CivilDate::new(2024, 5, 1)),
+        ..ClassifyContext::DEFAULT
+    };
+    let class = loan.classify_with(&ctx);
+    assert_eq!(class.signed, NaiveDateClassification::Normal);
+    assert_eq!(class.matures, NaiveDateClassification::Normal);
+    assert_eq!(class.paid, Some(NaiveDateClassification::ProbablyError));
+
+    // Later, 2070 is too far in the past for a maturity date
+    let ctx = ClassifyContext {
+        clock: Clock::Fixed(CivilDate::new(2080, 1, 1)),
+        ..ClassifyContext::DEFAULT
+    };
+    assert_eq!(
+        loan.classify_with(&ctx).matures,
+        NaiveDateClassification::ProbablyError
+    );
+    assert_eq!(
+        loan.class_key_with(&ctx).to_string(),
+        "{signed: Normal, matures: ProbablyError, paid: Some(ProbablyError)}"
+    );
+}
@@ -0,0 +1,50 @@
+use shrink::Cardinality;
+use shrink_macros::{ClassSpace, Classify, ClassifyEnum};
+
+#[derive(Classify, ClassSpace)]
+struct Fixture {
+    retries: u8,
+    kind: Kind,
+}
+
+#[derive(ClassifyEnum, ClassSpace)]
+enum Kind {
+    A,
+    B,
+}
+
+#[test]
+fn coverage_lists_unexercised_field_combinations() {
+    let fixtures = vec![
+        Fixture {
+            retries: 0,
+            kind: Kind::A,
+        },
+        Fixture {
+            retries: 3,
+            kind: Kind::A,
+        },
+        Fixture {
+            retries: 1,
+            kind: Kind::B,
+        },
+    ];
+
+    let report = shrink::coverage(fixtures);
+
+    assert_eq!(report.cardinality(), Cardinality::Finite(6));
+    assert_eq!(report.items(), 3);
+
+    let unseen: Vec<String> = report.unseen().map(|c| c.key.to_string()).collect();
+    assert_eq!(
+        unseen,
+        vec![
+            "{retries: Max, kind: A}",
+            "{retries: Zero, kind: B}",
+            "{retries: Max, kind: B}",
+        ]
+    );
+    assert!(report
+        .to_string()
+        .ends_with("\n3 of 6 classes seen in 3 items"));
+}
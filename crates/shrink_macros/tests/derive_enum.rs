@@ -0,0 +1,59 @@
+use shrink::Classify;
+
+#[derive(shrink_macros::Classify)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Rect { width: String, height: u32 },
+}
+
+#[derive(shrink_macros::ClassifyEnum)]
+enum SameShape {
+    Point,
+    Circle(f64),
+    Rect { width: String, height: u32 },
+}
+
+#[test]
+fn classify_derive_handles_enums() {
+    assert_ne!(Shape::Point.classify(), Shape::Circle(1.0).classify());
+    assert_ne!(Shape::Circle(1.0).classify(), Shape::Circle(0.0).classify());
+    assert_eq!(Shape::Circle(1.0).classify(), Shape::Circle(2.0).classify());
+    assert_ne!(
+        Shape::Rect {
+            width: "".to_string(),
+            height: 1
+        }
+        .classify(),
+        Shape::Rect {
+            width: "a".to_string(),
+            height: 1
+        }
+        .classify()
+    );
+}
+
+#[test]
+fn classify_derive_matches_classify_enum() {
+    assert_eq!(Shape::Point.classify(), SameShape::Point.classify());
+    assert_eq!(
+        Shape::Circle(f64::NAN).classify(),
+        SameShape::Circle(f64::NAN).classify()
+    );
+    assert_eq!(
+        Shape::Rect {
+            width: " ".to_string(),
+            height: 0
+        }
+        .classify(),
+        SameShape::Rect {
+            width: " ".to_string(),
+            height: 0
+        }
+        .classify()
+    );
+    assert_eq!(
+        Shape::Circle(1.0).class_key(),
+        SameShape::Circle(1.0).class_key()
+    );
+}
@@ -0,0 +1,142 @@
+use shrink::Classify;
+use shrink_macros::ClassifyEnum;
+use std::fmt::Debug;
+
+#[derive(ClassifyEnum)]
+enum Event<T: Classify> {
+    A(T),
+    B,
+}
+
+#[derive(ClassifyEnum)]
+enum Borrowed<'a> {
+    Name(&'a str),
+    Missing,
+}
+
+#[derive(ClassifyEnum)]
+enum Labelled<'a, T>
+where
+    T: Classify + Debug,
+{
+    Value { label: &'a str, value: T },
+    Nothing,
+}
+
+#[test]
+fn classify_enum_supports_type_parameters() {
+    assert_eq!(Event::A(1u8).classify(), Event::A(2u8).classify());
+    assert_ne!(Event::A(0u8).classify(), Event::A(2u8).classify());
+    assert_ne!(Event::<u8>::B.classify(), Event::A(2u8).classify());
+    assert_eq!(Event::A("a").class_key().to_string(), "A(NonEmpty)");
+}
+
+#[test]
+fn classify_enum_supports_lifetimes() {
+    let name = String::from("name");
+    assert_ne!(
+        Borrowed::Name(&name).classify(),
+        Borrowed::Name("").classify()
+    );
+    assert_ne!(
+        Borrowed::Name(&name).classify(),
+        Borrowed::Missing.classify()
+    );
+}
+
+#[test]
+fn classify_enum_supports_where_clauses() {
+    let a = Labelled::Value {
+        label: "a",
+        value: 1.0f64,
+    };
+    let b = Labelled::Value {
+        label: "b",
+        value: 2.0f64,
+    };
+    assert_eq!(a.classify(), b.classify());
+    assert_ne!(a.classify(), Labelled::<f64>::Nothing.classify());
+}
+
+#[derive(shrink_macros::Classify)]
+struct Wrapper<T> {
+    one: T,
+    many: Vec<T>,
+    maybe: Option<T>,
+}
+
+/// Does not implement Classify
+struct Opaque;
+
+#[derive(shrink_macros::Classify)]
+struct Marker<T> {
+    #[classify(skip)]
+    marker: std::marker::PhantomData<T>,
+    count: u8,
+}
+
+#[derive(shrink_macros::Classify)]
+#[classify(bound = "T: Classify, T::Output: Ord + std::hash::Hash + Eq")]
+struct Explicit<T> {
+    many: Vec<T>,
+}
+
+#[derive(shrink_macros::Classify)]
+enum Inferred<T> {
+    Many(Vec<T>),
+    None,
+}
+
+#[test]
+fn classify_infers_bounds_for_generic_structs() {
+    let a = Wrapper {
+        one: 1u8,
+        many: vec![0, 1],
+        maybe: None,
+    };
+    let b = Wrapper {
+        one: 2u8,
+        many: vec![1, 0, 2],
+        maybe: None,
+    };
+    assert_eq!(a.classify(), b.classify());
+
+    let a = Wrapper {
+        one: "a",
+        many: vec![],
+        maybe: Some(""),
+    };
+    assert_eq!(
+        a.class_key().to_string(),
+        "{one: NonEmpty, many: [len=0], maybe: Some(Empty)}"
+    );
+}
+
+#[test]
+fn classify_does_not_bound_skipped_fields() {
+    let a = Marker::<Opaque> {
+        marker: std::marker::PhantomData,
+        count: 0,
+    };
+    let b = Marker::<Opaque> {
+        marker: std::marker::PhantomData,
+        count: 1,
+    };
+    assert_ne!(a.classify(), b.classify());
+}
+
+#[test]
+fn classify_uses_explicit_bounds() {
+    assert_eq!(
+        Explicit { many: vec![1i8, 2] }.classify(),
+        Explicit { many: vec![3i8, 4] }.classify()
+    );
+    assert_eq!(
+        Inferred::Many(vec![1i8]).classify(),
+        Inferred::Many(vec![3i8]).classify()
+    );
+    assert_ne!(
+        Inferred::Many(vec![1i8]).classify(),
+        Inferred::<i8>::None.classify()
+    );
+}
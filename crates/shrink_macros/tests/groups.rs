@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+
+use shrink::Classify;
+use shrink_macros::{Classify, ClassifyEnum};
+
+#[derive(ClassifyEnum)]
+enum ErrorCode {
+    #[classify(group = "client")]
+    BadRequest,
+    #[classify(group = "client")]
+    NotFound,
+    #[classify(group = "client")]
+    Conflict,
+    #[classify(group = "server")]
+    Internal,
+    #[classify(group = "server")]
+    Unavailable,
+    Timeout,
+}
+
+/// A group named after a variant outside of it
+#[derive(ClassifyEnum)]
+enum Shadowed {
+    #[classify(group = "B")]
+    A,
+    B,
+}
+
+#[derive(Classify)]
+enum Message {
+    #[classify(ignore_fields)]
+    Text(String),
+    #[classify(ignore_fields)]
+    Binary { data: Vec<u8>, compressed: bool },
+    #[classify(group = "control")]
+    Ping(u8),
+    #[classify(group = "control")]
+    Pong(u8),
+    #[classify(group = "control", ignore_fields)]
+    Close(u16),
+}
+
+#[test]
+fn grouped_variants_share_a_class() {
+    use ErrorCode::*;
+
+    assert_eq!(BadRequest.classify(), NotFound.classify());
+    assert_eq!(BadRequest.classify(), Conflict.classify());
+    assert_eq!(Internal.classify(), Unavailable.classify());
+    assert_ne!(BadRequest.classify(), Internal.classify());
+    assert_ne!(BadRequest.classify(), Timeout.classify());
+    assert_ne!(Internal.classify(), Timeout.classify());
+
+    assert_eq!(NotFound.class_key().to_string(), "client");
+    assert_eq!(Timeout.class_key().to_string(), "Timeout");
+
+    let classes = shrink::shrink(vec![
+        BadRequest,
+        NotFound,
+        Internal,
+        Timeout,
+        Conflict,
+        Unavailable,
+    ]);
+    assert_eq!(classes.len(), 3);
+}
+
+#[test]
+fn groups_and_variants_do_not_share_tags() {
+    assert_ne!(Shadowed::A.classify(), Shadowed::B.classify());
+    assert_eq!(shrink::shrink(vec![Shadowed::A, Shadowed::B]).len(), 2);
+}
+
+#[test]
+fn ignore_fields_classifies_by_tag_only() {
+    assert_eq!(
+        Message::Text(String::new()).classify(),
+        Message::Text("a".to_string()).classify()
+    );
+    assert_eq!(
+        Message::Binary {
+            data: vec![],
+            compressed: false
+        }
+        .classify(),
+        Message::Binary {
+            data: vec![1, 2],
+            compressed: true
+        }
+        .classify()
+    );
+    assert_ne!(
+        Message::Text(String::new()).classify(),
+        Message::Binary {
+            data: vec![],
+            compressed: false
+        }
+        .classify()
+    );
+    assert_eq!(
+        Message::Text("a".to_string()).class_key().to_string(),
+        "Text"
+    );
+}
+
+#[test]
+fn grouped_variants_keep_their_fields() {
+    assert_eq!(Message::Ping(1).classify(), Message::Pong(2).classify());
+    assert_ne!(Message::Ping(0).classify(), Message::Pong(2).classify());
+    assert_eq!(Message::Close(0).classify(), Message::Close(1).classify());
+    assert_ne!(Message::Close(1).classify(), Message::Ping(1).classify());
+
+    assert_eq!(Message::Pong(0).class_key().to_string(), "control(Zero)");
+    assert_eq!(Message::Close(0).class_key().to_string(), "control");
+}
@@ -0,0 +1,98 @@
+//! Types over their `max_classes` budget fail to compile, which is covered
+//! by the `compile_fail` examples on `derive(Classify)`.
+#![allow(dead_code)]
+
+use shrink::Classify;
+use shrink_macros::{Classify, ClassifyEnum};
+
+#[derive(Classify)]
+#[classify(max_classes = 9)]
+struct Item {
+    first: u8,
+    second: String,
+    #[classify(skip)]
+    id: u64,
+}
+
+#[derive(Classify)]
+#[classify(typed)]
+struct Typed {
+    first: i8,
+    second: Option<bool>,
+}
+
+#[derive(ClassifyEnum)]
+#[classify(max_classes = 3)]
+enum Kind {
+    A,
+    B,
+    C,
+}
+
+#[derive(Classify)]
+enum Message {
+    #[classify(ignore_fields)]
+    Text(String),
+    Number {
+        value: i32,
+        exact: bool,
+    },
+    #[classify(group = "control")]
+    Ping(u8),
+    #[classify(group = "control")]
+    Pong(u8),
+    #[classify(group = "control", ignore_fields)]
+    Close(u16),
+}
+
+#[derive(Classify)]
+#[classify(max_classes = 4)]
+struct Wrapper<T> {
+    inner: Option<T>,
+}
+
+#[derive(Classify)]
+struct Unbounded {
+    items: Vec<u8>,
+}
+
+#[derive(Classify)]
+struct Custom {
+    #[classify(with = "is_even")]
+    value: u32,
+}
+
+fn is_even(value: &u32) -> bool {
+    value.is_multiple_of(2)
+}
+
+#[test]
+fn max_classes_multiply_over_fields() {
+    assert_eq!(Item::MAX_CLASSES, Some(9));
+    assert_eq!(Typed::MAX_CLASSES, Some(15));
+    assert_eq!(<(Item, Kind)>::MAX_CLASSES, Some(27));
+}
+
+#[test]
+fn max_classes_add_over_variants() {
+    assert_eq!(Kind::MAX_CLASSES, Some(3));
+
+    // Text, Number (5 * 2), control with a field (3), Close
+    assert_eq!(Message::MAX_CLASSES, Some(1 + 10 + 3 + 1));
+}
+
+#[test]
+fn max_classes_of_generic_types() {
+    assert_eq!(Wrapper::<u8>::MAX_CLASSES, Some(4));
+    assert_eq!(Wrapper::<bool>::MAX_CLASSES, Some(3));
+
+    // Classifying checks the budget of the instantiation
+    Wrapper { inner: Some(1u8) }.classify();
+}
+
+#[test]
+fn unbounded_types_have_no_max_classes() {
+    assert_eq!(Unbounded::MAX_CLASSES, None);
+    assert_eq!(Custom::MAX_CLASSES, None);
+    assert_eq!(Option::<Vec<u8>>::MAX_CLASSES, None);
+}
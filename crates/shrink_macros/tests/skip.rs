@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use shrink::Classify;
+use shrink_macros::{Classify, ClassifyEnum};
+
+/// Does not implement Classify
+struct Notes(String);
+
+#[derive(Classify)]
+struct Row {
+    #[classify(skip)]
+    id: u64,
+    value: i32,
+    #[classify(skip)]
+    notes: Notes,
+}
+
+#[derive(Classify)]
+struct Value {
+    value: i32,
+}
+
+#[derive(Classify)]
+struct TupleRow(#[classify(skip)] u64, i32, #[classify(skip)] Notes);
+
+#[derive(ClassifyEnum)]
+enum Event {
+    Created {
+        #[classify(skip)]
+        id: u64,
+        value: i32,
+    },
+    Noted(#[classify(skip)] Notes, bool),
+}
+
+fn notes(text: &str) -> Notes {
+    Notes(text.to_string())
+}
+
+#[test]
+fn skipped_struct_fields_are_ignored() {
+    let a = Row {
+        id: 0,
+        value: 1,
+        notes: notes("a"),
+    };
+    let b = Row {
+        id: u64::MAX,
+        value: 2,
+        notes: notes(""),
+    };
+    assert_eq!(a.classify(), b.classify());
+    assert_eq!(a.classify(), Value { value: 1 }.classify());
+    assert_eq!(a.class_key().to_string(), "{value: Positive}");
+
+    let a = TupleRow(0, -1, notes("a"));
+    let b = TupleRow(1, -2, notes("b"));
+    assert_eq!(a.classify(), b.classify());
+    assert_eq!(a.classify(), (-1i32,).classify());
+    assert_eq!(a.class_key().to_string(), "(Negative)");
+}
+
+#[test]
+fn skipped_variant_fields_are_ignored() {
+    assert_eq!(
+        Event::Created { id: 0, value: 1 }.classify(),
+        Event::Created { id: 1, value: 1 }.classify()
+    );
+    assert_ne!(
+        Event::Created { id: 0, value: 1 }.classify(),
+        Event::Created { id: 0, value: 0 }.classify()
+    );
+    assert_eq!(
+        Event::Noted(notes("a"), true).classify(),
+        Event::Noted(notes(""), true).classify()
+    );
+    assert_eq!(
+        Event::Noted(notes("a"), true).class_key().to_string(),
+        "Noted(true)"
+    );
+}
@@ -0,0 +1,86 @@
+use shrink::Classify;
+use shrink_macros::{Classify, ClassifyEnum};
+
+#[derive(Classify)]
+struct Test {
+    first: u8,
+    second: String,
+}
+
+#[derive(ClassifyEnum)]
+enum MyEnum {
+    Unit,
+    Tuple(u8),
+    Named { value: i64 },
+}
+
+// Derived keys may be stored outside the process, so a change to any of
+// these values is a breaking change
+#[test]
+fn derived_keys_are_stable() {
+    let t = Test {
+        first: 1,
+        second: "a".to_string(),
+    };
+    assert_eq!(t.classify(), 0x27e9a60eaf8ea293);
+
+    assert_eq!(MyEnum::Unit.classify(), 0xcb8138df8384f35b);
+    assert_eq!(MyEnum::Tuple(0).classify(), 0xa50ec8238cc53966);
+    assert_eq!(MyEnum::Named { value: -1 }.classify(), 0x3b33b8db1667306b);
+}
+
+mod reordered {
+    use shrink_macros::{Classify, ClassifyEnum};
+
+    #[derive(Classify)]
+    pub struct Test {
+        pub second: String,
+        pub first: u8,
+    }
+
+    #[derive(ClassifyEnum)]
+    pub enum MyEnum {
+        Named {
+            value: i64,
+        },
+        #[allow(dead_code)]
+        Added,
+        Tuple(u8),
+        Unit,
+    }
+}
+
+#[test]
+fn reordering_fields_and_variants_keeps_keys() {
+    let t = reordered::Test {
+        second: "a".to_string(),
+        first: 1,
+    };
+    assert_eq!(t.classify(), 0x27e9a60eaf8ea293);
+
+    assert_eq!(reordered::MyEnum::Unit.classify(), MyEnum::Unit.classify());
+    assert_eq!(
+        reordered::MyEnum::Tuple(0).classify(),
+        MyEnum::Tuple(0).classify()
+    );
+    assert_eq!(
+        reordered::MyEnum::Named { value: -1 }.classify(),
+        MyEnum::Named { value: -1 }.classify()
+    );
+}
+
+#[derive(ClassifyEnum)]
+enum WithIds {
+    #[classify(id = 1)]
+    Renamed,
+    #[classify(id = 2)]
+    Tuple(u8),
+    Plain,
+}
+
+#[test]
+fn explicit_ids_replace_variant_names() {
+    assert_eq!(WithIds::Renamed.classify(), 1);
+    assert_ne!(WithIds::Tuple(0).classify(), WithIds::Tuple(1).classify());
+    assert_eq!(WithIds::Plain.classify(), shrink::stable_hash("Plain"));
+}
@@ -0,0 +1,98 @@
+#![allow(dead_code)]
+
+use shrink::{Classify, IntClassification, StringClassification};
+use shrink_macros::{Classify, ClassifyEnum};
+
+#[derive(Classify)]
+#[classify(typed)]
+struct Item {
+    first: u8,
+    second: String,
+    third: Option<Kind>,
+    #[classify(skip)]
+    id: u64,
+    #[classify(with = "is_even")]
+    parity: u32,
+}
+
+#[derive(ClassifyEnum)]
+enum Kind {
+    A,
+    B,
+}
+
+fn is_even(value: &u32) -> bool {
+    value.is_multiple_of(2)
+}
+
+#[derive(Classify)]
+#[classify(typed)]
+struct Pair(i8, bool);
+
+#[derive(Classify)]
+struct Parent {
+    items: Vec<Item>,
+}
+
+fn item(first: u8, second: &str) -> Item {
+    Item {
+        first,
+        second: second.to_string(),
+        third: None,
+        id: 7,
+        parity: 2,
+    }
+}
+
+#[test]
+fn typed_output_can_be_matched() {
+    let classification = item(0, " ").classify();
+
+    assert!(matches!(
+        classification,
+        ItemClassification {
+            first: IntClassification::Zero,
+            second: StringClassification::Whitespace,
+            third: None,
+            ..
+        }
+    ));
+    assert_eq!(classification.parity, shrink::stable_hash(&true));
+
+    assert_eq!(
+        Pair(-1, true).classify(),
+        PairClassification(IntClassification::Negative, true)
+    );
+}
+
+#[test]
+fn typed_output_is_readable() {
+    assert_eq!(
+        format!("{:?}", Pair(i8::MAX, false).classify()),
+        "PairClassification(Max, false)"
+    );
+}
+
+#[test]
+fn typed_output_compares_like_u64_output() {
+    assert_eq!(item(1, "a").classify(), item(2, "b").classify());
+    assert_ne!(item(1, "a").classify(), item(0, "a").classify());
+
+    let with_kind = Item {
+        third: Some(Kind::A),
+        ..item(1, "a")
+    };
+    assert_ne!(with_kind.classify(), item(1, "a").classify());
+}
+
+#[test]
+fn typed_output_works_inside_vec() {
+    let a = Parent {
+        items: vec![item(1, "a"), item(0, "")],
+    };
+    let b = Parent {
+        items: vec![item(0, ""), item(2, "b"), item(3, "c")],
+    };
+
+    assert_eq!(a.classify(), b.classify());
+}
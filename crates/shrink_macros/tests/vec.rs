@@ -20,3 +20,27 @@ fn can_classify_macro_vec() {
 
     assert_eq!(a.classify(), b.classify());
 }
+
+#[derive(shrink_macros::Classify)]
+struct Mixed {
+    strings: Vec<String>,
+    floats: Vec<f64>,
+    dates: Vec<chrono::NaiveDate>,
+}
+
+#[test]
+fn can_classify_macro_vec_of_any_builtin() {
+    let a = Mixed {
+        strings: vec!["a".to_string(), "".to_string()],
+        floats: vec![1.0, f64::NAN],
+        dates: vec![chrono::NaiveDate::default()],
+    };
+
+    let b = Mixed {
+        strings: vec!["".to_string(), "b".to_string()],
+        floats: vec![f64::NAN, 2.0],
+        dates: vec![chrono::NaiveDate::default()],
+    };
+
+    assert_eq!(a.classify(), b.classify());
+}
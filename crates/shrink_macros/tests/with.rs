@@ -0,0 +1,115 @@
+use shrink::{Classify, StableHash};
+use shrink_macros::{Classify, ClassifyEnum};
+use std::hash::Hasher;
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum StatusClass {
+    Success,
+    ClientError,
+    ServerError,
+    Other,
+}
+
+impl StableHash for StatusClass {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u8(match self {
+            StatusClass::Success => 0,
+            StatusClass::ClientError => 1,
+            StatusClass::ServerError => 2,
+            StatusClass::Other => 3,
+        });
+    }
+}
+
+fn status_class(code: &u32) -> StatusClass {
+    match code {
+        200..=299 => StatusClass::Success,
+        400..=499 => StatusClass::ClientError,
+        500..=599 => StatusClass::ServerError,
+        _ => StatusClass::Other,
+    }
+}
+
+mod classifiers {
+    pub fn is_long(s: &str) -> bool {
+        s.len() > 3
+    }
+}
+
+#[derive(Classify)]
+struct Response {
+    #[classify(with = "status_class")]
+    status: u32,
+    #[classify(with = "classifiers::is_long")]
+    body: String,
+}
+
+#[derive(ClassifyEnum)]
+enum Event {
+    Request(#[classify(with = "status_class")] u32),
+    Response {
+        #[classify(with = "self::classifiers::is_long")]
+        body: String,
+        ok: bool,
+    },
+}
+
+fn response(status: u32, body: &str) -> Response {
+    Response {
+        status,
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn custom_classifier_replaces_classify_in_structs() {
+    assert_eq!(response(200, "").classify(), response(204, "").classify());
+    assert_ne!(response(200, "").classify(), response(404, "").classify());
+    assert_ne!(response(404, "").classify(), response(503, "").classify());
+    assert_ne!(
+        response(200, "").classify(),
+        response(200, "long").classify()
+    );
+    assert_eq!(response(200, "a").classify(), response(200, "").classify());
+
+    assert_eq!(
+        response(200, "").class_key(),
+        response(201, "b").class_key()
+    );
+}
+
+#[test]
+fn custom_classifier_replaces_classify_in_enums() {
+    assert_eq!(
+        Event::Request(404).classify(),
+        Event::Request(400).classify()
+    );
+    assert_ne!(
+        Event::Request(404).classify(),
+        Event::Request(500).classify()
+    );
+    assert_eq!(
+        Event::Response {
+            body: "abcd".to_string(),
+            ok: true
+        }
+        .classify(),
+        Event::Response {
+            body: "abcde".to_string(),
+            ok: true
+        }
+        .classify()
+    );
+    assert_ne!(
+        Event::Response {
+            body: "ab".to_string(),
+            ok: true
+        }
+        .classify(),
+        Event::Response {
+            body: "abcde".to_string(),
+            ok: true
+        }
+        .classify()
+    );
+}